@@ -0,0 +1,466 @@
+use multi_token_standard::core::MultiTokenCore;
+use near_account::NearAccountsPluginNonExternal;
+use near_sdk::{
+    assert_one_yocto,
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    collections::{LookupMap, TreeMap},
+    env,
+    json_types::{U128, U64},
+    serde::{Deserialize, Serialize},
+    AccountId, Balance, Promise,
+};
+
+use crate::{
+    types::MTTokenId,
+    utils::{FEE_DENOMINATOR, U256},
+    Contract,
+};
+
+/// How long a buyout auction runs until the contract owner changes it, 7 days in nanoseconds
+pub const DEFAULT_BUYOUT_DURATION: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+/// Share of the supply which has to vote on the reserve price before a buyout can start, as
+/// the numerator of FEE_DENOMINATOR in utils.rs
+pub const DEFAULT_RESERVE_QUORUM_NUMERATOR: u128 = FEE_DENOMINATOR / 2;
+/// Share of the supply a holder needs to vote on the reserve price, as the numerator of
+/// FEE_DENOMINATOR in utils.rs
+pub const DEFAULT_MIN_VOTE_NUMERATOR: u128 = FEE_DENOMINATOR / 100;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuctionSerial {
+    pub bidder: AccountId,
+    pub bid: U128,
+    pub ends_at: U64,
+    pub settled: bool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BuyoutInfoSerial {
+    /// The supply-weighted median of the voted reserve prices, set once a quorum voted
+    pub reserve_price: Option<U128>,
+    /// The balance weighing all the votes
+    pub voted: U128,
+    pub auction: Option<AuctionSerial>,
+    pub proceeds: U128,
+}
+
+/// The highest bid for the underlying tokens of a vault
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Debug)]
+pub struct Auction {
+    pub bidder: AccountId,
+    /// Held by the contract until the bidder is outbid or the auction is settled
+    pub bid: Balance,
+    /// Block timestamp in nanoseconds from which no more bids are taken
+    pub ends_at: u64,
+    /// Set once the underlying tokens were released to the winner. This is a permanent action
+    pub settled: bool,
+}
+
+/// A holder's vote on the reserve price of a vault. It weighs as much as the voter's balance when
+/// voting, and is lowered along with the balance as fractions are moved away or burned
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Debug)]
+pub struct ReserveVote {
+    pub reserve_price: Balance,
+    pub weight: Balance,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct BuyoutInfo {
+    /// The sum of the weights of all the votes
+    pub voted: Balance,
+    /// The summed weight of the votes for each reserve price, ordered to find their median
+    pub reserve_weights: TreeMap<Balance, Balance>,
+    pub auction: Option<Auction>,
+    /// The part of the winning bid which was not redeemed by fraction holders yet
+    pub proceeds: Balance,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Buyouts {
+    buyouts: LookupMap<MTTokenId, BuyoutInfo>,
+    /// The reserve price vote of each holder who voted
+    votes: LookupMap<(MTTokenId, AccountId), ReserveVote>,
+    /// How long an auction runs from its first bid, in nanoseconds
+    duration: u64,
+    /// Share of the supply which has to vote before a buyout can start, connoted by the numerator
+    /// of FEE_DENOMINATOR in utils.rs
+    quorum_numerator: u128,
+    /// Share of the supply a holder needs to vote, connoted by the numerator of FEE_DENOMINATOR in
+    /// utils.rs. Keeps the number of votes on a vault bounded
+    min_vote_numerator: u128,
+}
+
+pub trait BuyoutFns {
+    /// Votes for the lowest bid a buyout of the vault can start with, weighted by the caller's
+    /// balance of `mt_id`, which must be at least the minimum to vote. Voting again updates the
+    /// weight to the current balance, `None` withdraws the caller's vote. The attached deposit
+    /// must cover the storage of a new vote
+    fn buyout_vote_reserve_price(&mut self, mt_id: MTTokenId, reserve_price: Option<U128>);
+
+    /// Starts an auction for all the underlying tokens of the vault. Only possible once a quorum
+    /// of the supply voted on the reserve price. The attached deposit is the first bid and must
+    /// be at least the reserve price
+    fn buyout_start(&mut self, mt_id: MTTokenId);
+
+    /// Bids the attached deposit, which must be higher than the current bid. The previous bidder
+    /// is refunded
+    fn buyout_bid(&mut self, mt_id: MTTokenId);
+
+    /// Releases the underlying tokens into the internal balance of the highest bidder once the
+    /// auction ended. Callable by anyone
+    fn buyout_settle(&mut self, mt_id: MTTokenId);
+
+    /// Burns the caller's unlocked fractions of a bought out vault and pays out their pro-rata
+    /// share of the winning bid
+    fn buyout_redeem(&mut self, mt_id: MTTokenId) -> U128;
+
+    fn buyout_update_duration(&mut self, update: U64);
+
+    fn buyout_update_quorum(&mut self, update: U128);
+
+    fn buyout_update_min_vote(&mut self, update: U128);
+
+    fn buyout_info(&self, mt_id: MTTokenId) -> BuyoutInfoSerial;
+
+    fn buyout_reserve_vote(&self, mt_id: MTTokenId, account_id: AccountId) -> Option<U128>;
+
+    fn buyout_get_duration(&self) -> U64;
+
+    fn buyout_get_quorum(&self) -> U128;
+
+    fn buyout_get_min_vote(&self) -> U128;
+}
+
+impl Buyouts {
+    pub(crate) fn new() -> Self {
+        Self {
+            buyouts: LookupMap::new("byt".as_bytes()),
+            votes: LookupMap::new("bytv".as_bytes()),
+            duration: DEFAULT_BUYOUT_DURATION,
+            quorum_numerator: DEFAULT_RESERVE_QUORUM_NUMERATOR,
+            min_vote_numerator: DEFAULT_MIN_VOTE_NUMERATOR,
+        }
+    }
+}
+
+impl Auction {
+    fn to_serial(&self) -> AuctionSerial {
+        AuctionSerial {
+            bidder: self.bidder.clone(),
+            bid: U128::from(self.bid),
+            ends_at: U64::from(self.ends_at),
+            settled: self.settled,
+        }
+    }
+}
+
+impl BuyoutInfo {
+    fn add_vote(&mut self, vote: &ReserveVote) {
+        self.voted += vote.weight;
+        let weight = self.reserve_weights.get(&vote.reserve_price).unwrap_or(0) + vote.weight;
+        self.reserve_weights.insert(&vote.reserve_price, &weight);
+    }
+
+    fn remove_vote(&mut self, vote: &ReserveVote) {
+        self.voted -= vote.weight;
+        let weight = self.reserve_weights.get(&vote.reserve_price).unwrap_or(0) - vote.weight;
+        if weight == 0 {
+            self.reserve_weights.remove(&vote.reserve_price);
+        } else {
+            self.reserve_weights.insert(&vote.reserve_price, &weight);
+        }
+    }
+
+    fn to_serial(&self, voted: Balance, reserve_price: Option<Balance>) -> BuyoutInfoSerial {
+        BuyoutInfoSerial {
+            reserve_price: reserve_price.map(U128::from),
+            voted: U128::from(voted),
+            auction: self.auction.as_ref().map(|auction| auction.to_serial()),
+            proceeds: U128::from(self.proceeds),
+        }
+    }
+}
+
+impl Contract {
+    /// Records a newly fractionalized vault, which has no reserve price votes yet
+    pub(crate) fn buyout_create(&mut self, mt_id: &MTTokenId) {
+        let info = BuyoutInfo {
+            voted: 0,
+            reserve_weights: TreeMap::new(format!("byt-w{}", mt_id).as_bytes()),
+            auction: None,
+            proceeds: 0,
+        };
+        self.buyouts.buyouts.insert(mt_id, &info);
+    }
+
+    pub(crate) fn buyout_vote_reserve_price_internal(
+        &mut self,
+        mt_id: MTTokenId,
+        reserve_price: Option<Balance>,
+    ) {
+        let initial_storage_usage = env::storage_usage();
+        let caller = env::predecessor_account_id();
+        let mut info = self.get_buyout(&mt_id);
+        assert!(info.auction.is_none(), "A buyout of {} already started", mt_id);
+
+        let key = (mt_id.clone(), caller.clone());
+        if let Some(vote) = self.buyouts.votes.remove(&key) {
+            info.remove_vote(&vote);
+        }
+        if let Some(reserve_price) = reserve_price {
+            let balance = self.mt.balance_of(caller.clone(), mt_id.clone()).0;
+            let min_balance = self.buyout_min_vote_balance(&mt_id);
+            assert!(
+                balance > 0 && balance >= min_balance,
+                "Expected the caller to hold at least {} of {} to vote",
+                min_balance,
+                mt_id
+            );
+            let vote = ReserveVote { reserve_price, weight: balance };
+            info.add_vote(&vote);
+            self.buyouts.votes.insert(&key, &vote);
+        }
+        self.buyouts.buyouts.insert(&mt_id, &info);
+
+        self.check_storage_deposit(
+            env::storage_usage().saturating_sub(initial_storage_usage),
+            None,
+        );
+    }
+
+    pub(crate) fn buyout_start_internal(&mut self, mt_id: MTTokenId) {
+        let mut info = self.get_buyout(&mt_id);
+        assert!(info.auction.is_none(), "A buyout of {} already started", mt_id);
+        self.nft_fractionalize_assert_wrapped(&mt_id);
+        let (_, reserve_price) = self.buyout_tally(&mt_id, &info);
+        let reserve_price = reserve_price
+            .unwrap_or_else(|| panic!("Not enough of {} voted on the reserve price", mt_id));
+
+        let bid = env::attached_deposit();
+        assert!(bid >= reserve_price, "Expected a bid of at least {}", reserve_price);
+        let bidder = env::predecessor_account_id();
+        self.assert_bidder_registered(&bidder);
+
+        info.auction = Some(Auction {
+            bidder,
+            bid,
+            ends_at: env::block_timestamp() + self.buyouts.duration,
+            settled: false,
+        });
+        self.buyouts.buyouts.insert(&mt_id, &info);
+    }
+
+    pub(crate) fn buyout_bid_internal(&mut self, mt_id: MTTokenId) {
+        let mut info = self.get_buyout(&mt_id);
+        let bidder = env::predecessor_account_id();
+        self.assert_bidder_registered(&bidder);
+
+        let auction = Self::get_auction(&mut info, &mt_id);
+        assert!(env::block_timestamp() < auction.ends_at, "The buyout of {} has ended", mt_id);
+        let bid = env::attached_deposit();
+        assert!(bid > auction.bid, "Expected a bid higher than {}", auction.bid);
+
+        // Return the escrowed bid to the outbid account
+        Promise::new(auction.bidder.clone()).transfer(auction.bid);
+        auction.bidder = bidder;
+        auction.bid = bid;
+        self.buyouts.buyouts.insert(&mt_id, &info);
+    }
+
+    pub(crate) fn buyout_settle_internal(&mut self, mt_id: MTTokenId) {
+        let mut info = self.get_buyout(&mt_id);
+        let auction = Self::get_auction(&mut info, &mt_id);
+        assert!(
+            env::block_timestamp() >= auction.ends_at,
+            "The buyout of {} has not ended yet",
+            mt_id
+        );
+        assert!(!auction.settled, "The buyout of {} was already settled", mt_id);
+        auction.settled = true;
+        let winner = auction.bidder.clone();
+        info.proceeds = auction.bid;
+        self.buyouts.buyouts.insert(&mt_id, &info);
+
+        // The winning bid is split over the supply as it is now, releasing closes minting
+        self.nft_fractionalize_release_underlying(&mt_id, &winner);
+
+        // Fractions the contract still holds for a sale are redeemed for the sale's owner
+        if let Some(sale_owner) = self.sales_owner(&mt_id) {
+            let contract_id = env::current_account_id();
+            let held = self.mt.balance_of(contract_id.clone(), mt_id.clone()).0;
+            if held > 0 {
+                let share = self.buyout_burn_for_share(&mt_id, &contract_id, held);
+                Promise::new(sale_owner).transfer(share);
+            }
+        }
+    }
+
+    pub(crate) fn buyout_redeem_internal(&mut self, mt_id: MTTokenId) -> U128 {
+        assert_one_yocto();
+        let caller = env::predecessor_account_id();
+        let balance = self.mt.balance_of(caller.clone(), mt_id.clone()).0;
+        let amount = balance - self.mt.internal_locked_balance_of(&mt_id, &caller);
+        assert!(amount > 0, "Expected the caller to hold unlocked fractions of {}", mt_id);
+
+        let share = self.buyout_burn_for_share(&mt_id, &caller, amount);
+        Promise::new(caller).transfer(share);
+        U128::from(share)
+    }
+
+    pub(crate) fn buyout_update_duration_internal(&mut self, update: U64) {
+        assert_eq!(self.owner_id, env::predecessor_account_id(), "Caller must be the owner");
+        self.buyouts.duration = update.0;
+    }
+
+    pub(crate) fn buyout_update_quorum_internal(&mut self, update: U128) {
+        assert_eq!(self.owner_id, env::predecessor_account_id(), "Caller must be the owner");
+        assert!(
+            update.0 <= FEE_DENOMINATOR,
+            "Expected the quorum to be at most {}",
+            FEE_DENOMINATOR
+        );
+        self.buyouts.quorum_numerator = update.0;
+    }
+
+    pub(crate) fn buyout_update_min_vote_internal(&mut self, update: U128) {
+        assert_eq!(self.owner_id, env::predecessor_account_id(), "Caller must be the owner");
+        assert!(
+            update.0 <= FEE_DENOMINATOR,
+            "Expected the minimum to vote to be at most {}",
+            FEE_DENOMINATOR
+        );
+        self.buyouts.min_vote_numerator = update.0;
+    }
+
+    pub(crate) fn buyout_info_internal(&self, mt_id: MTTokenId) -> BuyoutInfoSerial {
+        let info = self.get_buyout(&mt_id);
+        let (voted, reserve_price) = self.buyout_tally(&mt_id, &info);
+        info.to_serial(voted, reserve_price)
+    }
+
+    pub(crate) fn buyout_reserve_vote_internal(
+        &self,
+        mt_id: MTTokenId,
+        account_id: AccountId,
+    ) -> Option<U128> {
+        self.buyouts.votes.get(&(mt_id, account_id)).map(|vote| U128::from(vote.reserve_price))
+    }
+
+    pub(crate) fn buyout_get_duration_internal(&self) -> U64 {
+        U64::from(self.buyouts.duration)
+    }
+
+    pub(crate) fn buyout_get_quorum_internal(&self) -> U128 {
+        U128::from(self.buyouts.quorum_numerator)
+    }
+
+    pub(crate) fn buyout_get_min_vote_internal(&self) -> U128 {
+        U128::from(self.buyouts.min_vote_numerator)
+    }
+
+    /// Lowers the weight of the vote of `account_id` to the balance of `mt_id` it holds once
+    /// `burned` more is burned, and drops the vote once that balance is below the minimum to vote.
+    /// Called as fractions leave the account
+    pub(crate) fn buyout_follow_balance(
+        &mut self,
+        mt_id: &MTTokenId,
+        account_id: &AccountId,
+        burned: Balance,
+    ) {
+        let key = (mt_id.clone(), account_id.clone());
+        let mut vote = match self.buyouts.votes.get(&key) {
+            Some(vote) => vote,
+            None => return,
+        };
+        let balance =
+            self.mt.balance_of(account_id.clone(), mt_id.clone()).0.saturating_sub(burned);
+        if balance >= vote.weight {
+            return;
+        }
+        let mut info = self.get_buyout(mt_id);
+        info.remove_vote(&vote);
+        if balance > 0 && balance >= self.buyout_min_vote_balance(mt_id) {
+            vote.weight = balance;
+            info.add_vote(&vote);
+            self.buyouts.votes.insert(&key, &vote);
+        } else {
+            self.buyouts.votes.remove(&key);
+        }
+        self.buyouts.buyouts.insert(mt_id, &info);
+    }
+
+    /// Panics once a buyout of the vault started, as its supply and underlying tokens have to
+    /// stay as they are for the auction
+    pub(crate) fn buyout_assert_not_started(&self, mt_id: &MTTokenId) {
+        if let Some(info) = self.buyouts.buyouts.get(mt_id) {
+            assert!(info.auction.is_none(), "A buyout of {} already started", mt_id);
+        }
+    }
+
+    /// Burns `amount` fractions of `account_id` from a settled vault and takes their pro-rata
+    /// share out of the proceeds
+    fn buyout_burn_for_share(
+        &mut self,
+        mt_id: &MTTokenId,
+        account_id: &AccountId,
+        amount: Balance,
+    ) -> Balance {
+        let mut info = self.get_buyout(mt_id);
+        assert!(
+            info.auction.as_ref().map(|auction| auction.settled).unwrap_or(false),
+            "The buyout of {} is not settled",
+            mt_id
+        );
+        let supply = self.mt.total_supply(mt_id.clone()).0;
+        let share = (U256::from(info.proceeds) * U256::from(amount) / U256::from(supply)).as_u128();
+
+        self.mt_internal_burn(mt_id, account_id, amount, None);
+        info.proceeds -= share;
+        self.buyouts.buyouts.insert(mt_id, &info);
+        share
+    }
+
+    /// Returns the balance which voted on the reserve price, along with the weighted median of the
+    /// votes once it reaches the quorum
+    fn buyout_tally(&self, mt_id: &MTTokenId, info: &BuyoutInfo) -> (Balance, Option<Balance>) {
+        let voted = info.voted;
+        let supply = self.mt.total_supply(mt_id.clone()).0;
+        if voted == 0 || voted < Self::calculate_fee(supply, self.buyouts.quorum_numerator) {
+            return (voted, None);
+        }
+
+        // Summing the weights in ascending price order, the lowest price at which the votes at or
+        // below it reach half of the voted balance
+        let mut cumulative = 0;
+        let median = info.reserve_weights.iter().find(|(_, weight)| {
+            cumulative += weight;
+            cumulative >= voted - voted / 2
+        });
+        (voted, median.map(|(reserve_price, _)| reserve_price))
+    }
+
+    fn buyout_min_vote_balance(&self, mt_id: &MTTokenId) -> Balance {
+        let supply = self.mt.total_supply(mt_id.clone()).0;
+        Self::calculate_fee(supply, self.buyouts.min_vote_numerator)
+    }
+
+    /// The winner receives the underlying tokens as internal balances
+    fn assert_bidder_registered(&self, bidder: &AccountId) {
+        assert!(
+            self.accounts.get_account(bidder).is_some(),
+            "Expected the bidder to be registered"
+        );
+    }
+
+    fn get_auction<'a>(info: &'a mut BuyoutInfo, mt_id: &MTTokenId) -> &'a mut Auction {
+        info.auction.as_mut().unwrap_or_else(|| panic!("No buyout of {} has started", mt_id))
+    }
+
+    fn get_buyout(&self, mt_id: &MTTokenId) -> BuyoutInfo {
+        self.buyouts
+            .buyouts
+            .get(mt_id)
+            .unwrap_or_else(|| panic!("Cannot find the buyout info for {}", mt_id))
+    }
+}
@@ -1,5 +1,10 @@
+use multi_token_standard::hooks::MultiTokenHooks;
+use multi_token_standard::metadata::{MtContractMetadata, MT_METADATA_SPEC};
 use multi_token_standard::{
-    impl_multi_token_core, impl_multi_token_metadata, impl_multi_token_storage, MultiToken,
+    impl_multi_token_approval, impl_multi_token_checkpoint, impl_multi_token_core,
+    impl_multi_token_enumeration, impl_multi_token_metadata, impl_multi_token_pause,
+    impl_multi_token_lock, impl_multi_token_payout, impl_multi_token_permit,
+    impl_multi_token_storage, MultiToken,
 };
 use near_account::{
     impl_near_accounts_plugin, Account, AccountDeposits, Accounts, NearAccountPlugin,
@@ -11,13 +16,18 @@ use near_contract_standards::storage_management::StorageManagement as _StorageMa
 use near_internal_balances_plugin::token_id::TokenId;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LazyOption, UnorderedMap};
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{U128, U64};
 use near_sdk::{
     env, near_bindgen, AccountId, Balance, BorshStorageKey, PanicOnDefault, PromiseOrValue,
 };
+use std::collections::HashMap;
+use buyout::{BuyoutFns, BuyoutInfoSerial, Buyouts};
+use minting::{MintAuthoritySerial, Minting, MintingFns};
 use nft_fractionalizer::{NftFractionalizer, NftFractionalizerFns};
 use sales::{SaleOptions, SaleOptionsSerial, Sales, SalesFns};
 
+pub mod buyout;
+pub mod minting;
 pub mod nft_fractionalizer;
 pub mod sales;
 pub mod types;
@@ -42,6 +52,11 @@ enum StorageKey {
     MultiTokenOwner,
     MultiTokenMetadata,
     MultiTokenSupply,
+    MultiTokenApproval,
+    MultiTokenEnumeration,
+    ContractMetadata,
+    MultiTokenCheckpoint,
+    MultiTokenRoyalty,
 }
 
 #[near_bindgen]
@@ -49,10 +64,13 @@ enum StorageKey {
 pub struct Contract {
     pub accounts: Accounts<AccountInfo>,
     pub mt: MultiToken,
+    pub metadata: LazyOption<MtContractMetadata>,
     pub owner_id: AccountId,
     pub treasury_id: AccountId,
     pub nft_fractionalizer: NftFractionalizer,
     pub sales: Sales,
+    pub minting: Minting,
+    pub buyouts: Buyouts,
 }
 
 impl_near_accounts_plugin!(Contract, accounts, AccountInfo);
@@ -60,21 +78,65 @@ impl_near_accounts_plugin!(Contract, accounts, AccountInfo);
 impl_near_balance_plugin!(Contract, accounts, AccountInfo, internal_balance);
 impl_multi_token_core!(Contract, mt);
 impl_multi_token_storage!(Contract, mt);
-impl_multi_token_metadata!(Contract, mt);
+impl_multi_token_metadata!(Contract, mt, metadata);
+impl_multi_token_approval!(Contract, mt);
+impl_multi_token_enumeration!(Contract, mt);
+impl_multi_token_pause!(Contract, mt);
+impl_multi_token_checkpoint!(Contract, mt);
+impl_multi_token_payout!(Contract, mt);
+impl_multi_token_lock!(Contract, mt);
+impl_multi_token_permit!(Contract, mt);
+
+// Keeps reserve price votes from weighing more than the balance of the voter whenever tokens
+// leave an account, see `buyout_follow_balance`
+impl MultiTokenHooks for Contract {
+    fn after_transfer(
+        &mut self,
+        _sender_id: &AccountId,
+        owner_ids: &[AccountId],
+        _receiver_id: &AccountId,
+        token_ids: &[MTTokenId],
+        _amounts: &[U128],
+    ) {
+        for (owner_id, token_id) in owner_ids.iter().zip(token_ids) {
+            self.buyout_follow_balance(token_id, owner_id, 0);
+        }
+    }
+
+    fn before_burn(
+        &mut self,
+        _sender_id: &AccountId,
+        owner_ids: &[AccountId],
+        token_ids: &[MTTokenId],
+        amounts: &[U128],
+    ) {
+        for ((owner_id, token_id), amount) in owner_ids.iter().zip(token_ids).zip(amounts) {
+            self.buyout_follow_balance(token_id, owner_id, amount.0);
+        }
+    }
+}
 
 #[near_bindgen]
 impl Contract {
     /// Initializes the contract with the given total supply owned by the given `owner_id` with
     /// the given fungible token metadata.
+    /// * `metadata`: The contract level metadata shared by every token, defaults to the
+    ///   current metadata spec with the name "Token Generator"
     #[init]
     pub fn new(
         owner_id: Option<AccountId>,
         treasury: Option<AccountId>,
         nft_mint_fee_numerator: Option<U128>,
         sale_fee_numerator: Option<U128>,
+        metadata: Option<MtContractMetadata>,
     ) -> Self {
         let owner_id = owner_id.unwrap_or(env::predecessor_account_id());
         let treasury_id = treasury.unwrap_or(env::predecessor_account_id());
+        let metadata = metadata.unwrap_or(MtContractMetadata {
+            spec: MT_METADATA_SPEC.to_string(),
+            name: "Token Generator".to_string(),
+        });
+        metadata.assert_valid();
 
         let mut this = Contract {
             accounts: Accounts::new(),
@@ -83,8 +145,15 @@ impl Contract {
                 owner_id.clone(),
                 Some(StorageKey::MultiTokenMetadata),
                 StorageKey::MultiTokenSupply,
+                Some(StorageKey::MultiTokenApproval),
+                Some(StorageKey::MultiTokenEnumeration),
+                Some(StorageKey::MultiTokenCheckpoint),
+                Some(StorageKey::MultiTokenRoyalty),
             ),
+            metadata: LazyOption::new(StorageKey::ContractMetadata, Some(&metadata)),
             sales: Sales::new(sale_fee_numerator.map(|v| v.into()).unwrap_or(0)),
+            minting: Minting::new(),
+            buyouts: Buyouts::new(),
             owner_id: owner_id.clone(),
             nft_fractionalizer: NftFractionalizer::new(
                 nft_mint_fee_numerator.map(|v| v.into()).unwrap_or(0),
@@ -92,6 +161,9 @@ impl Contract {
             treasury_id: treasury_id.clone(),
         };
 
+        // Fractions can be sent to accounts which have not registered for them yet
+        this.mt.auto_register_receivers = true;
+
         let default_account = Account::default_from_account_id(owner_id.clone());
         this.accounts.accounts.insert(&owner_id, &default_account);
 
@@ -124,22 +196,32 @@ impl NftFractionalizerFns for Contract {
     #[payable]
     fn nft_fractionalize(
         &mut self,
-        nfts: Vec<TokenId>,
+        underlying: Vec<(TokenId, U128)>,
         mt_id: types::MTTokenId,
         amount: U128,
         mt_owner: Option<AccountId>,
-        token_metadata: multi_token_standard::metadata::MultiTokenMetadata,
+        token_metadata: multi_token_standard::metadata::TokenMetadataAll,
         sale_amount: Option<U128>,
         sale_price_per_token: Option<U128>,
+        max_supply: Option<U128>,
+        royalty: Option<HashMap<AccountId, u32>>,
+        redemptions: Option<Vec<(TokenId, U128)>>,
     ) {
         self.nft_fractionalize_internal(
-            nfts,
+            underlying.into_iter().map(|(token, amount)| (token, amount.into())).collect(),
             mt_id,
             amount.into(),
             mt_owner,
             token_metadata,
             sale_amount.map(|v| v.into()),
             sale_price_per_token.map(|v| v.into()),
+            max_supply.map(|v| v.into()),
+            royalty,
+            redemptions
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(token, amount)| (token, amount.into()))
+                .collect(),
         );
     }
 
@@ -148,15 +230,105 @@ impl NftFractionalizerFns for Contract {
         self.nft_fractionalize_unwrap_internal(mt_id, release_to);
     }
 
+    #[payable]
+    fn nft_fractionalize_redeem(&mut self, mt_id: types::MTTokenId, token_id: TokenId) {
+        self.nft_fractionalize_redeem_internal(mt_id, token_id);
+    }
+
     fn nft_fractionalize_get_mint_fee(&self) -> U128 {
         self.nft_fractionalize_get_mint_fee_internal()
     }
 
-    fn nft_fractionalize_get_underlying(&self, mt_id: types::MTTokenId) -> Vec<TokenId> {
+    fn nft_fractionalize_get_underlying(&self, mt_id: types::MTTokenId) -> Vec<(TokenId, U128)> {
         self.nft_fractionalize_get_underlying_internal(mt_id)
     }
 
+    fn nft_fractionalize_get_redemptions(&self, mt_id: types::MTTokenId) -> Vec<(TokenId, U128)> {
+        self.nft_fractionalize_get_redemptions_internal(mt_id)
+    }
+
     fn nft_fractionalize_update_mint_fee(&mut self, update: U128) {
         self.nft_fractionalize_update_mint_fee_internal(update);
     }
 }
+
+#[near_bindgen]
+impl MintingFns for Contract {
+    #[payable]
+    fn mt_mint(&mut self, mt_id: types::MTTokenId, amount: U128, receiver_id: Option<AccountId>) {
+        self.mt_mint_internal(mt_id, amount.into(), receiver_id)
+    }
+
+    #[payable]
+    fn mt_set_minter(&mut self, mt_id: types::MTTokenId, minter: AccountId) {
+        self.mt_set_minter_internal(mt_id, minter)
+    }
+
+    #[payable]
+    fn mt_renounce_minting(&mut self, mt_id: types::MTTokenId) {
+        self.mt_renounce_minting_internal(mt_id)
+    }
+
+    fn mt_mint_authority(&self, mt_id: types::MTTokenId) -> MintAuthoritySerial {
+        self.mt_mint_authority_internal(mt_id)
+    }
+}
+
+#[near_bindgen]
+impl BuyoutFns for Contract {
+    #[payable]
+    fn buyout_vote_reserve_price(&mut self, mt_id: types::MTTokenId, reserve_price: Option<U128>) {
+        self.buyout_vote_reserve_price_internal(mt_id, reserve_price.map(|v| v.into()))
+    }
+
+    #[payable]
+    fn buyout_start(&mut self, mt_id: types::MTTokenId) {
+        self.buyout_start_internal(mt_id)
+    }
+
+    #[payable]
+    fn buyout_bid(&mut self, mt_id: types::MTTokenId) {
+        self.buyout_bid_internal(mt_id)
+    }
+
+    fn buyout_settle(&mut self, mt_id: types::MTTokenId) {
+        self.buyout_settle_internal(mt_id)
+    }
+
+    #[payable]
+    fn buyout_redeem(&mut self, mt_id: types::MTTokenId) -> U128 {
+        self.buyout_redeem_internal(mt_id)
+    }
+
+    fn buyout_update_duration(&mut self, update: U64) {
+        self.buyout_update_duration_internal(update)
+    }
+
+    fn buyout_update_quorum(&mut self, update: U128) {
+        self.buyout_update_quorum_internal(update)
+    }
+
+    fn buyout_update_min_vote(&mut self, update: U128) {
+        self.buyout_update_min_vote_internal(update)
+    }
+
+    fn buyout_info(&self, mt_id: types::MTTokenId) -> BuyoutInfoSerial {
+        self.buyout_info_internal(mt_id)
+    }
+
+    fn buyout_reserve_vote(&self, mt_id: types::MTTokenId, account_id: AccountId) -> Option<U128> {
+        self.buyout_reserve_vote_internal(mt_id, account_id)
+    }
+
+    fn buyout_get_duration(&self) -> U64 {
+        self.buyout_get_duration_internal()
+    }
+
+    fn buyout_get_quorum(&self) -> U128 {
+        self.buyout_get_quorum_internal()
+    }
+
+    fn buyout_get_min_vote(&self) -> U128 {
+        self.buyout_get_min_vote_internal()
+    }
+}
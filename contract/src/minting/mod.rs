@@ -0,0 +1,163 @@
+use multi_token_standard::core::MultiTokenCore;
+use near_sdk::{
+    assert_one_yocto,
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    collections::LookupMap,
+    env,
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+    AccountId, Balance,
+};
+
+use crate::{
+    types::{MTTokenId, MTTokenType},
+    Contract,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintAuthoritySerial {
+    pub minter: Option<AccountId>,
+    pub max_supply: Option<U128>,
+}
+
+/// Who may issue more of an FT type token and how far its supply may grow
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Debug)]
+pub struct MintAuthority {
+    /// Set to None once minting has been renounced. This is a permanent action
+    pub minter: Option<AccountId>,
+    /// Hard cap on the total supply, recorded when the token is created
+    pub max_supply: Option<Balance>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Minting {
+    authorities: LookupMap<MTTokenId, MintAuthority>,
+}
+
+pub trait MintingFns {
+    /// Mints `amount` more of an existing token, only callable by the token's minter and not while
+    /// any of its underlying tokens can be redeemed. The attached deposit must cover the storage
+    /// used for a new holder
+    /// * `receiver_id`: The account receiving the tokens, defaults to the minter
+    fn mt_mint(&mut self, mt_id: MTTokenId, amount: U128, receiver_id: Option<AccountId>);
+
+    /// Hands the minter role of the token over to `minter`
+    fn mt_set_minter(&mut self, mt_id: MTTokenId, minter: AccountId);
+
+    /// Permanently gives up the minter role, fixing the token's supply
+    fn mt_renounce_minting(&mut self, mt_id: MTTokenId);
+
+    fn mt_mint_authority(&self, mt_id: MTTokenId) -> MintAuthoritySerial;
+}
+
+impl Minting {
+    pub(crate) fn new() -> Self {
+        Self { authorities: LookupMap::new("mnt".as_bytes()) }
+    }
+}
+
+impl MintAuthority {
+    fn to_serial(&self) -> MintAuthoritySerial {
+        MintAuthoritySerial {
+            minter: self.minter.clone(),
+            max_supply: self.max_supply.map(U128::from),
+        }
+    }
+}
+
+impl Contract {
+    /// Records the minter and supply cap of a newly created FT type token
+    pub(crate) fn minting_create(
+        &mut self,
+        mt_id: &MTTokenId,
+        minter: AccountId,
+        max_supply: Option<Balance>,
+    ) {
+        if let Some(max_supply) = max_supply {
+            let supply = self.mt.total_supply(mt_id.clone()).0;
+            assert!(supply <= max_supply, "Expected the max supply to be at least {}", supply);
+        }
+        self.minting.authorities.insert(mt_id, &MintAuthority { minter: Some(minter), max_supply });
+    }
+
+    pub(crate) fn mt_mint_internal(
+        &mut self,
+        mt_id: MTTokenId,
+        amount: Balance,
+        receiver_id: Option<AccountId>,
+    ) {
+        let initial_storage_usage = env::storage_usage();
+        let caller = env::predecessor_account_id();
+        self.buyout_assert_not_started(&mt_id);
+        self.nft_fractionalize_assert_no_redemptions(&mt_id);
+        let authority = self.get_mint_authority(&mt_id);
+        assert!(
+            authority.minter.as_ref() == Some(&caller),
+            "Only the minter of {} can mint more tokens",
+            mt_id
+        );
+        if let Some(max_supply) = authority.max_supply {
+            // Burned tokens still count towards the cap, it limits how much is ever minted
+            let minted = self.mt.mt_supply(mt_id.clone()).minted.0;
+            assert!(
+                minted
+                    .checked_add(amount)
+                    .map(|new_minted| new_minted <= max_supply)
+                    .unwrap_or(false),
+                "Minting {} would exceed the max supply of {}",
+                amount,
+                max_supply
+            );
+        }
+
+        // The metadata recorded at creation is kept as is
+        let receiver_id = receiver_id.unwrap_or(caller);
+        self.mt.internal_mint(mt_id, MTTokenType::Ft, Some(amount), receiver_id, None);
+
+        self.check_storage_deposit(env::storage_usage() - initial_storage_usage, None);
+    }
+
+    pub(crate) fn mt_set_minter_internal(&mut self, mt_id: MTTokenId, minter: AccountId) {
+        assert_one_yocto();
+        let mut authority = self.get_mint_authority(&mt_id);
+        assert!(
+            authority.minter == Some(env::predecessor_account_id()),
+            "Only the minter of {} can set a new minter",
+            mt_id
+        );
+        authority.minter = Some(minter);
+        self.minting.authorities.insert(&mt_id, &authority);
+    }
+
+    pub(crate) fn mt_renounce_minting_internal(&mut self, mt_id: MTTokenId) {
+        assert_one_yocto();
+        let mut authority = self.get_mint_authority(&mt_id);
+        assert!(
+            authority.minter == Some(env::predecessor_account_id()),
+            "Only the minter of {} can renounce minting",
+            mt_id
+        );
+        authority.minter = None;
+        self.minting.authorities.insert(&mt_id, &authority);
+    }
+
+    /// Permanently stops any further minting of the token, e.g. once its underlying is released
+    pub(crate) fn minting_close(&mut self, mt_id: &MTTokenId) {
+        if let Some(mut authority) = self.minting.authorities.get(mt_id) {
+            authority.minter = None;
+            self.minting.authorities.insert(mt_id, &authority);
+        }
+    }
+
+    pub(crate) fn mt_mint_authority_internal(&self, mt_id: MTTokenId) -> MintAuthoritySerial {
+        self.get_mint_authority(&mt_id).to_serial()
+    }
+
+    fn get_mint_authority(&self, mt_id: &MTTokenId) -> MintAuthority {
+        self.minting
+            .authorities
+            .get(mt_id)
+            .unwrap_or_else(|| panic!("Cannot find the mint authority for {}", mt_id))
+    }
+}
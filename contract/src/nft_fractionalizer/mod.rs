@@ -1,4 +1,4 @@
-use multi_token_standard::{core::MultiTokenCore, metadata::MultiTokenMetadata, Token};
+use multi_token_standard::{core::MultiTokenCore, metadata::TokenMetadataAll, Token};
 use near_account::Account;
 use near_internal_balances_plugin::{
     InternalBalanceHandlers, SudoInternalBalanceHandlers, TokenId,
@@ -12,6 +12,7 @@ use near_sdk::{
     serde::{self, Deserialize, Serialize},
     AccountId, Balance,
 };
+use std::collections::HashMap;
 
 use crate::{
     sales::SaleOptions,
@@ -20,7 +21,10 @@ use crate::{
 };
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct NftInfo {
-    nfts: Vec<TokenId>,
+    /// The tokens held for the mt and their amounts, always 1 for NFTs
+    underlying: Vec<(TokenId, Balance)>,
+    /// The amount of the mt to burn for taking a single underlying token out on its own
+    redemptions: Vec<(TokenId, Balance)>,
     /// Set to true after unwrapping an NFT. This is a permanent action and marks the token as
     /// 'deleted'
     unwrapped: bool,
@@ -33,24 +37,42 @@ pub struct NftFractionalizer {
 
 pub trait NftFractionalizerFns {
     /// Mints the new token
+    /// * `underlying`: The NFTs, FTs and MTs to lock up for the new token, with their amounts. The
+    ///    amount of an NFT must be 1
     /// * `mt_id`: The id of the new token. This id must be new and cannot have existed previously on this contract
+    /// * `max_supply`: Optional hard cap on the supply the `mt_owner`, as the token's minter, can grow it to
+    /// * `royalty`: Optional royalties paid on secondary sales of the token, in basis points of the
+    ///    sale price, ex. 250 for 2.5%
+    /// * `redemptions`: Optional amounts of the new token for which single underlying tokens can be
+    ///    redeemed on their own, ex. a quarter of `amount` for one of four NFTs. The amounts add up
+    ///    to at most `amount`, or exactly `amount` when every underlying token is listed, and the
+    ///    token can't be minted while any of them is left
     fn nft_fractionalize(
         &mut self,
-        nfts: Vec<TokenId>,
+        underlying: Vec<(TokenId, U128)>,
         mt_id: MTTokenId,
         amount: U128,
         mt_owner: Option<AccountId>,
-        token_metadata: MultiTokenMetadata,
+        token_metadata: TokenMetadataAll,
         sale_amount: Option<U128>,
         sale_price_per_token: Option<U128>,
+        max_supply: Option<U128>,
+        royalty: Option<HashMap<AccountId, u32>>,
+        redemptions: Option<Vec<(TokenId, U128)>>,
     );
 
-    /// Deletes the mt and releases the nfts.
+    /// Deletes the mt and releases the underlying tokens.
     fn nft_fractionalize_unwrap(&mut self, mt_id: MTTokenId, release_to: Option<AccountId>);
 
+    /// Burns the amount of the mt the redemption table lists for `token_id` from the caller and
+    /// releases just that underlying token to them
+    fn nft_fractionalize_redeem(&mut self, mt_id: MTTokenId, token_id: TokenId);
+
     fn nft_fractionalize_update_mint_fee(&mut self, update: U128);
 
-    fn nft_fractionalize_get_underlying(&self, mt_id: MTTokenId) -> Vec<TokenId>;
+    fn nft_fractionalize_get_underlying(&self, mt_id: MTTokenId) -> Vec<(TokenId, U128)>;
+
+    fn nft_fractionalize_get_redemptions(&self, mt_id: MTTokenId) -> Vec<(TokenId, U128)>;
 
     fn nft_fractionalize_get_mint_fee(&self) -> U128;
 }
@@ -62,11 +84,18 @@ impl NftFractionalizer {
 }
 
 impl Contract {
-    fn insert_mt(&mut self, mt: &MTTokenId, nfts: Vec<TokenId>) {
+    fn insert_mt(
+        &mut self,
+        mt: &MTTokenId,
+        underlying: Vec<(TokenId, Balance)>,
+        redemptions: Vec<(TokenId, Balance)>,
+    ) {
         match self.nft_fractionalizer.mt_to_nfts.get(mt) {
             Some(_) => panic!("Should not get here, but only new 'mt's can be added"),
             None => {
-                self.nft_fractionalizer.mt_to_nfts.insert(mt, &NftInfo { nfts, unwrapped: false });
+                self.nft_fractionalizer
+                    .mt_to_nfts
+                    .insert(mt, &NftInfo { underlying, redemptions, unwrapped: false });
             }
         }
     }
@@ -75,23 +104,56 @@ impl Contract {
     /// * `mt_id`: The id of the new token. This id must be new and cannot have existed previously on this contract
     pub(crate) fn nft_fractionalize_internal(
         &mut self,
-        nfts: Vec<TokenId>,
+        underlying: Vec<(TokenId, Balance)>,
         mt_id: MTTokenId,
         amount: u128,
         mt_owner: Option<AccountId>,
-        token_metadata: MultiTokenMetadata,
+        token_metadata: TokenMetadataAll,
         sale_amount: Option<Balance>,
         sale_price_per_token: Option<Balance>,
+        max_supply: Option<Balance>,
+        royalty: Option<HashMap<AccountId, u32>>,
+        redemptions: Vec<(TokenId, Balance)>,
     ) {
         let minter = env::predecessor_account_id();
         let mt_owner = mt_owner.unwrap_or(minter.clone());
         let initial_storage_usage = env::storage_usage();
 
+        // Reject bad metadata before touching any balances
+        token_metadata.assert_valid();
+
         // Subtract from the user's balances
-        for token in &nfts {
-            Self::assert_nft_type(token);
-            self.internal_balance_subtract(&minter, &token, 1);
+        for (idx, (token, token_amount)) in underlying.iter().enumerate() {
+            Self::assert_underlying_amount(token, *token_amount);
+            assert!(
+                !underlying[..idx].iter().any(|(other, _)| other == token),
+                "Expected each underlying token to be listed once"
+            );
+            self.internal_balance_subtract(&minter, &token, *token_amount);
         }
+        for (idx, (token, redeem_amount)) in redemptions.iter().enumerate() {
+            assert!(
+                underlying.iter().any(|(underlying_token, _)| underlying_token == token),
+                "Expected only underlying tokens in the redemption table"
+            );
+            assert!(
+                !redemptions[..idx].iter().any(|(other, _)| other == token),
+                "Expected each token to be listed once in the redemption table"
+            );
+            assert!(*redeem_amount > 0, "Expected a positive redemption amount");
+        }
+        let redeemable = redemptions
+            .iter()
+            .try_fold(0u128, |total, (_, redeem_amount)| total.checked_add(*redeem_amount));
+        assert!(
+            redeemable.map(|redeemable| redeemable <= amount).unwrap_or(false),
+            "Expected the redemption amounts to add up to at most the supply"
+        );
+        // Otherwise fractions would be left over once every underlying token is redeemed
+        assert!(
+            redemptions.len() < underlying.len() || redeemable == Some(amount),
+            "Expected the redemptions of all the underlying tokens to add up to the supply"
+        );
 
         // create the mt
         self.mint_mt(
@@ -101,9 +163,14 @@ impl Contract {
             mt_owner.clone(),
             token_metadata,
         );
+        if let Some(royalty) = royalty {
+            self.mt.internal_set_royalty(&mt_id, royalty);
+        }
 
         // Insert the mt into local data
-        self.insert_mt(&mt_id, nfts);
+        self.insert_mt(&mt_id, underlying, redemptions);
+        self.minting_create(&mt_id, mt_owner.clone(), max_supply);
+        self.buyout_create(&mt_id);
 
         match (sale_amount, sale_price_per_token) {
             (Some(sale_amount), Some(sale_price_per_token)) => {
@@ -113,7 +180,7 @@ impl Contract {
                 );
                 // Transfer the sale tokens to the current contract after registering it
                 self.mt.internal_register_account(mt_id.clone(), &env::current_account_id());
-                self.mt.internal_transfer(
+                self.mt_internal_transfer(
                     &mt_owner,
                     &env::current_account_id(),
                     &mt_id,
@@ -141,13 +208,14 @@ impl Contract {
         );
     }
 
-    /// Deletes the mt and releases the nfts.
+    /// Deletes the mt and releases the underlying tokens.
     pub(crate) fn nft_fractionalize_unwrap_internal(
         &mut self,
         mt_id: MTTokenId,
         release_to: Option<AccountId>,
     ) {
         assert_one_yocto();
+        self.buyout_assert_not_started(&mt_id);
         let caller = env::predecessor_account_id();
         let caller_balance = self.mt.balance_of_batch(caller.clone(), vec![mt_id.clone()])[0].0;
         let total_supply = self.mt.total_supply(mt_id.clone()).0;
@@ -157,16 +225,82 @@ impl Contract {
         );
 
         // burn the supply of the entire token, but keep around the metadata for future reference
-        self.mt.internal_withdraw(&mt_id, &caller, total_supply);
+        self.mt_internal_burn(&mt_id, &caller, total_supply, None);
 
-        // redeposit the NFT's into the caller's account
-        let nfts = self.nft_fractionalizer.mt_to_nfts.get(&mt_id).unwrap();
+        // redeposit the underlying tokens into the caller's account
         let release_to = release_to.as_ref().unwrap_or(&caller);
-        for nft in nfts.nfts {
-            self.internal_balance_increase(&release_to, &nft, 1);
+        self.nft_fractionalize_release_underlying(&mt_id, release_to);
+    }
+
+    /// Burns the redemption amount of `token_id` from the caller and releases just that token.
+    pub(crate) fn nft_fractionalize_redeem_internal(
+        &mut self,
+        mt_id: MTTokenId,
+        token_id: TokenId,
+    ) {
+        assert_one_yocto();
+        self.buyout_assert_not_started(&mt_id);
+        self.nft_fractionalize_assert_wrapped(&mt_id);
+        let caller = env::predecessor_account_id();
+        let mut info = self.nft_fractionalizer.mt_to_nfts.get(&mt_id).unwrap();
+        let redemption_idx = info
+            .redemptions
+            .iter()
+            .position(|(token, _)| token == &token_id)
+            .expect("The token cannot be redeemed on its own");
+        let (_, redeem_amount) = info.redemptions.remove(redemption_idx);
+
+        // burn the redeemed fractions, which lowers the supply the rest of the mt is split over
+        self.mt_internal_burn(&mt_id, &caller, redeem_amount, None);
+
+        let underlying_idx =
+            info.underlying.iter().position(|(token, _)| token == &token_id).unwrap();
+        let (token, amount) = info.underlying.remove(underlying_idx);
+        self.internal_balance_increase(&caller, &token, amount);
+        self.nft_fractionalizer.mt_to_nfts.insert(&mt_id, &info);
+
+        // Once its last token is redeemed the mt is left without an underlying
+        if info.underlying.is_empty() {
+            self.nft_fractionalize_release_underlying(&mt_id, &caller);
+        }
+    }
+
+    /// Moves all the underlying tokens of the mt into the internal balance of `release_to`, closes
+    /// minting and marks the mt as unwrapped
+    pub(crate) fn nft_fractionalize_release_underlying(
+        &mut self,
+        mt_id: &MTTokenId,
+        release_to: &AccountId,
+    ) {
+        self.nft_fractionalize_assert_wrapped(mt_id);
+        self.minting_close(mt_id);
+        let mut info = self.nft_fractionalizer.mt_to_nfts.get(mt_id).unwrap();
+        for (token, amount) in &info.underlying {
+            self.internal_balance_increase(release_to, token, *amount);
+        }
+        info.redemptions.clear();
+        info.unwrapped = true;
+        self.nft_fractionalizer.mt_to_nfts.insert(mt_id, &info);
+    }
+
+    /// Redemption amounts are fixed against the supply at fractionalization, so it can only grow
+    /// once none are left
+    pub(crate) fn nft_fractionalize_assert_no_redemptions(&self, mt_id: &MTTokenId) {
+        if let Some(info) = self.nft_fractionalizer.mt_to_nfts.get(mt_id) {
+            assert!(
+                info.redemptions.is_empty(),
+                "{} can't be minted while its underlying tokens can be redeemed",
+                mt_id
+            );
         }
     }
 
+    pub(crate) fn nft_fractionalize_assert_wrapped(&self, mt_id: &MTTokenId) {
+        let info =
+            self.nft_fractionalizer.mt_to_nfts.get(mt_id).expect("The queried mt does not exist");
+        assert!(!info.unwrapped, "The underlying tokens of {} were already released", mt_id);
+    }
+
     pub(crate) fn nft_fractionalize_get_mint_fee_internal(&self) -> U128 {
         U128::from(self.nft_fractionalizer.mint_fee)
     }
@@ -174,8 +308,19 @@ impl Contract {
     pub(crate) fn nft_fractionalize_get_underlying_internal(
         &self,
         mt_id: MTTokenId,
-    ) -> Vec<TokenId> {
-        self.nft_fractionalizer.mt_to_nfts.get(&mt_id).expect("The queried mt does not exist").nfts
+    ) -> Vec<(TokenId, U128)> {
+        let info =
+            self.nft_fractionalizer.mt_to_nfts.get(&mt_id).expect("The queried mt does not exist");
+        info.underlying.into_iter().map(|(token, amount)| (token, U128::from(amount))).collect()
+    }
+
+    pub(crate) fn nft_fractionalize_get_redemptions_internal(
+        &self,
+        mt_id: MTTokenId,
+    ) -> Vec<(TokenId, U128)> {
+        let info =
+            self.nft_fractionalizer.mt_to_nfts.get(&mt_id).expect("The queried mt does not exist");
+        info.redemptions.into_iter().map(|(token, amount)| (token, U128::from(amount))).collect()
     }
 
     pub(crate) fn nft_fractionalize_update_mint_fee_internal(&mut self, update: U128) {
@@ -183,10 +328,10 @@ impl Contract {
         self.nft_fractionalizer.mint_fee = update.0;
     }
 
-    fn assert_nft_type(token: &TokenId) {
+    fn assert_underlying_amount(token: &TokenId, amount: Balance) {
         match token {
-            TokenId::FT { .. } => panic!("Expected an NFT token type"),
-            _ => (),
+            TokenId::NFT { .. } => assert_eq!(amount, 1, "Expected the amount of an NFT to be 1"),
+            _ => assert!(amount > 0, "Expected a positive amount of each underlying token"),
         };
     }
 }
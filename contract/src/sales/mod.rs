@@ -1,4 +1,4 @@
-use multi_token_standard::{core::MultiTokenCore, metadata::MultiTokenMetadata, Token};
+use multi_token_standard::{core::MultiTokenCore, Token};
 use near_account::Account;
 use near_sdk::{
     assert_one_yocto,
@@ -103,7 +103,7 @@ impl Contract {
         self.transfer_fee(amount_to_owner, &sale.owner);
 
         // Transfer the token's to the buyer's account
-        self.mt.internal_transfer(&env::current_account_id(), &caller, &mt_id, amount, None);
+        self.mt_internal_transfer(&env::current_account_id(), &caller, &mt_id, amount, None);
 
         sale.sold += amount;
         self.sales.sales.insert(&mt_id, &sale);
@@ -117,4 +117,9 @@ impl Contract {
         }
         self.sales.sales.insert(mt_id, &sale);
     }
+
+    /// The account paid for the fractions sold by the contract, if the token has a sale
+    pub(crate) fn sales_owner(&self, mt_id: &MTTokenId) -> Option<AccountId> {
+        self.sales.sales.get(mt_id).map(|sale| sale.owner)
+    }
 }
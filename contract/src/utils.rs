@@ -1,6 +1,6 @@
-use multi_token_standard::{metadata::MultiTokenMetadata, TokenType};
+use multi_token_standard::metadata::TokenMetadataAll;
 use near_account::NearAccountsPluginNonExternal;
-use near_sdk::{env, require, AccountId, Balance, Promise};
+use near_sdk::{env, AccountId, Balance, Promise};
 use uint::construct_uint;
 
 use crate::{
@@ -39,73 +39,17 @@ impl Contract {
 }
 
 impl Contract {
-    /// Taken from [multi-token-standard-impl/examples/multi-token/mt](https://github.com/shipsgold/multi-token-standard-impl/blob/ec874d2e010908160f6c73555bde119943b96736/examples/multi-token/mt/src/lib.rs#L66)
-    /// slightly modified to allow for fee collections
+    /// Mints through `MultiToken::internal_mint` so that the multi token extensions (enumeration etc.)
+    /// stay in sync. Fee collection is handled by the callers.
     pub(crate) fn mint_mt(
         &mut self,
         token_id: MTTokenId,
         token_type: MTTokenType,
         amount: Option<u128>,
         token_owner_id: AccountId,
-        token_metadata: MultiTokenMetadata,
+        token_metadata: TokenMetadataAll,
     ) {
-        // Every token must have a token type and every NFT type cannot be re-minted
-        match self.mt.token_type_index.get(&token_id) {
-            Some(MTTokenType::Ft) => {
-                require!(
-                    token_type == MTTokenType::Ft,
-                    "Type must be of FT time tokenId already exists"
-                )
-            }
-            Some(MTTokenType::Nft) => {
-                env::panic_str("Attempting to mint already minted NFT");
-            }
-            None => {
-                self.mt.token_type_index.insert(&token_id, &token_type);
-            }
-        }
-
-        let owner_id: AccountId = token_owner_id;
-        // Core behavior: every token must have an owner
-        match token_type {
-            TokenType::Ft => {
-                if amount.is_none() {
-                    env::panic_str("Amount must be specified for Ft type tokens");
-                }
-                // advance the prefix index before insertion
-                let amt = u128::from(amount.unwrap());
-                //create LookupMap for balances
-                match self.mt.ft_owners_by_id.get(&token_id) {
-                    Some(mut balances) => {
-                        let current_bal = balances.get(&owner_id).unwrap_or(0);
-                        // TODO not quite safe
-                        if amt == 0 {
-                            env::panic_str("error: amount should be greater than 0")
-                        }
-                        balances.insert(&owner_id, &(current_bal + amt));
-                        let supply = self.mt.ft_token_supply_by_id.get(&token_id).unwrap();
-                        self.mt.ft_token_supply_by_id.insert(&token_id, &(supply + amt));
-                    }
-                    None => {
-                        let mut balances = self.mt.internal_new_ft_balances();
-                        // insert amount into balances
-                        balances.insert(&owner_id, &amt);
-                        self.mt.ft_owners_by_id.insert(&token_id, &balances);
-                        self.mt.ft_token_supply_by_id.insert(&token_id, &amt);
-                    }
-                }
-            }
-            TokenType::Nft => {
-                self.mt.nft_owner_by_id.insert(&token_id, &owner_id);
-            }
-        }
-        // Metadata extension: Save metadata, keep variable around to return later.
-        // Note that check above already panicked if metadata extension in use but no metadata
-        // provided to call.
-        self.mt
-            .token_metadata_by_id
-            .as_mut()
-            .and_then(|by_id| by_id.insert(&token_id, &token_metadata));
+        self.mt.internal_mint(token_id, token_type, amount, token_owner_id, Some(token_metadata));
     }
 
     /// Taken from [multi-token-standard-impl/examples/multi-token/mt](https://github.com/shipsgold/multi-token-standard-impl/blob/ec874d2e010908160f6c73555bde119943b96736/examples/multi-token/mt/src/lib.rs#L51)
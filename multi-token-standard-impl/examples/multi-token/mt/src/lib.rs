@@ -13,8 +13,9 @@ NOTES:
   - To prevent the deployed contract from being modified or deleted, it should not have any access
     keys on its account.
 */
-use multi_token_standard::metadata::MultiTokenMetadata;
-use multi_token_standard::MultiToken;
+use multi_token_standard::hooks::MultiTokenHooks;
+use multi_token_standard::metadata::{BaseTokenMetadata, TokenMetadata, TokenMetadataAll};
+use multi_token_standard::{MultiToken, TokenId, TokenType};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LazyOption, LookupMap};
 use near_sdk::json_types::U128;
@@ -34,6 +35,10 @@ enum StorageKey {
     MultiTokenOwner,
     MultiTokenMetadata,
     MultiTokenSupply,
+    MultiTokenApproval,
+    MultiTokenEnumeration,
+    MultiTokenCheckpoint,
+    MultiTokenRoyalty,
 }
 
 #[near_bindgen]
@@ -47,6 +52,10 @@ impl Contract {
                 owner_id,
                 Some(StorageKey::MultiTokenMetadata),
                 StorageKey::MultiTokenSupply,
+                Some(StorageKey::MultiTokenApproval),
+                Some(StorageKey::MultiTokenEnumeration),
+                Some(StorageKey::MultiTokenCheckpoint),
+                Some(StorageKey::MultiTokenRoyalty),
             ),
         }
     }
@@ -72,7 +81,7 @@ impl Contract {
         token_type: TokenType,
         amount: Option<U128>,
         token_owner_id: AccountId,
-        token_metadata: Option<MultiTokenMetadata>,
+        token_metadata: Option<TokenMetadataAll>,
     ) {
         let initial_storage_usage = env::storage_usage();
         require!(env::predecessor_account_id() == self.token.owner_id, "Unauthorized");
@@ -81,87 +90,56 @@ impl Contract {
         //     env::panic_str("Must provide metadata");
         // }
 
-        // Every token must have a token type and every NFT type cannot be re-minted
-        match self.token.token_type_index.get(&token_id) {
-            Some(TokenType::Ft) => {
-                require!(
-                    token_type == TokenType::Ft,
-                    "Type must be of FT time tokenId already exists"
-                )
-            }
-            Some(TokenType::Nft) => {
-                env::panic_str("Attempting to mint already minted NFT");
-            }
-            None => {
-                self.token.token_type_index.insert(&token_id, &token_type);
-            }
-        }
-
-        let owner_id: AccountId = token_owner_id;
-        // Core behavior: every token must have an owner
-        match token_type {
-            TokenType::Ft => {
-                if amount.is_none() {
-                    env::panic_str("Amount must be specified for Ft type tokens");
-                }
-                // advance the prefix index before insertion
-                let amt = u128::from(amount.unwrap());
-                //create LookupMap for balances
-                match self.token.ft_owners_by_id.get(&token_id) {
-                    Some(mut balances) => {
-                        let current_bal = balances.get(&owner_id).unwrap_or(0);
-                        // TODO not quite safe
-                        if amt == 0 {
-                            env::panic_str("error: amount should be greater than 0")
-                        }
-                        balances.insert(&owner_id, &(current_bal + amt));
-                        let supply = self.token.ft_token_supply_by_id.get(&token_id).unwrap();
-                        self.token.ft_token_supply_by_id.insert(&token_id, &(supply + amt));
-                    }
-                    None => {
-                        let mut balances = self.token.internal_new_ft_balances();
-                        // insert amount into balances
-                        balances.insert(&owner_id, &amt);
-                        self.token.ft_owners_by_id.insert(&token_id, &balances);
-                        self.token.ft_token_supply_by_id.insert(&token_id, &amt);
-                    }
-                }
-            }
-            TokenType::Nft => {
-                self.token.nft_owner_by_id.insert(&token_id, &owner_id);
-            }
-        }
-        // Metadata extension: Save metadata, keep variable around to return later.
-        // Note that check above already panicked if metadata extension in use but no metadata
-        // provided to call.
-        self.token.token_metadata_by_id.as_mut().and_then(|by_id| {
-            by_id.insert(
-                &token_id,
-                &token_metadata.as_ref().unwrap_or(&MultiTokenMetadata {
-                    spec: "aa".to_string(),   // required, essentially a version like "mt-1.0.0"
-                    name: "aa".to_string(),   // required, ex. "Mosaics"
-                    symbol: "aa".to_string(), // required, ex. "MOSIAC"
-                    icon: None,               // Data URL
-                    base_uri: None, // Centralized gateway known to have reliable access to decentralized storage assets referenced by `reference` or `media` URLs
-                    decimals: Some(12), // precision decimals for tokens that need this information
-                    reference: None, // URL to a JSON file with more info
-                    reference_hash: None, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
-                    title: None,          // ex. "Arch Nemesis: Mail Carrier" or "Parcel #5055"
-                    description: None,    // free-form description
-                    media: None, // URL to associated media, preferably to decentralized, content-addressed storage
-                    media_hash: None, // Base64-encoded sha256 hash of content referenced by the `media` field. Required if `media` is included.
-                    copies: None, // number of copies of this set of metadata in existence when token was minted.
-                    issued_at: None, // ISO 8601 datetime when token was issued or minted
-                    expires_at: None, // ISO 8601 datetime when token expires
-                    starts_at: None, // ISO 8601 datetime when token starts being valid
-                    updated_at: None, // ISO 8601 datetime when token was last updated
-                    extra: None, // anything extra the NFT wants to store on-chain. Can be stringified JSON.
-                }),
-            )
+        // Metadata extension: fall back to placeholder metadata when none is provided.
+        let token_metadata = token_metadata.unwrap_or(TokenMetadataAll {
+            base: BaseTokenMetadata {
+                id: "aa".to_string(),   // required, unique id of this base metadata
+                name: "aa".to_string(), // required, ex. "Mosaics"
+                symbol: Some("aa".to_string()), // ex. "MOSIAC"
+                icon: None,             // Data URL
+                decimals: Some(12), // precision decimals for tokens that need this information
+                base_uri: None, // Centralized gateway known to have reliable access to decentralized storage assets referenced by `reference` or `media` URLs
+                reference: None, // URL to a JSON file with more info
+                reference_hash: None, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
+                copies: None, // number of copies of this set of metadata in existence when token was minted.
+            },
+            token: TokenMetadata::default(),
         });
+        self.token.internal_mint(
+            token_id,
+            token_type,
+            amount.map(|amount| amount.into()),
+            token_owner_id,
+            Some(token_metadata),
+        );
         // Return any extra attached deposit not used for storage
         self.refund_deposit(env::storage_usage() - initial_storage_usage);
     }
 }
+
+impl MultiTokenHooks for Contract {
+    /// Nothing can move tokens out of the contract account, so they can't be sent to it
+    fn before_transfer(
+        &mut self,
+        _sender_id: &AccountId,
+        _owner_ids: &[AccountId],
+        receiver_id: &AccountId,
+        _token_ids: &[TokenId],
+        _amounts: &[U128],
+    ) {
+        require!(
+            receiver_id != &env::current_account_id(),
+            "Tokens can't be transferred to the contract"
+        );
+    }
+}
+
 multi_token_standard::impl_multi_token_core!(Contract, token);
 multi_token_standard::impl_multi_token_storage!(Contract, token);
+multi_token_standard::impl_multi_token_approval!(Contract, token);
+multi_token_standard::impl_multi_token_enumeration!(Contract, token);
+multi_token_standard::impl_multi_token_pause!(Contract, token);
+multi_token_standard::impl_multi_token_checkpoint!(Contract, token);
+multi_token_standard::impl_multi_token_payout!(Contract, token);
+multi_token_standard::impl_multi_token_lock!(Contract, token);
+multi_token_standard::impl_multi_token_permit!(Contract, token);
@@ -54,6 +54,7 @@ impl MultiTokenReceiver for TokenReceiver {
     fn mt_on_transfer(
         &mut self,
         sender_id: AccountId,
+        previous_owner_ids: Vec<AccountId>,
         token_ids: Vec<TokenId>,
         amounts: Vec<U128>,
         msg: String,
@@ -65,7 +66,13 @@ impl MultiTokenReceiver for TokenReceiver {
             &self.multi_token_account_id,
             "Only supports the one semi-fungible token contract"
         );
-        log!("in mt_on_transfer; sender_id={}, token_id={:?}, msg={}", &sender_id, &token_ids, msg);
+        log!(
+            "in mt_on_transfer; sender_id={}, previous_owner_ids={:?}, token_id={:?}, msg={}",
+            &sender_id,
+            &previous_owner_ids,
+            &token_ids,
+            msg
+        );
         match msg.as_str() {
             "return-it-now" => PromiseOrValue::Value(amounts),
             "return-it-later" => {
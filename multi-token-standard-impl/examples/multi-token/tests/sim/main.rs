@@ -1,2 +1,10 @@
 mod utils;
-mod test_core;
\ No newline at end of file
+mod test_core;
+mod test_approval;
+mod test_enumeration;
+mod test_events;
+mod test_storage;
+mod test_pause;
+mod test_checkpoint;
+mod test_lock;
+mod test_permit;
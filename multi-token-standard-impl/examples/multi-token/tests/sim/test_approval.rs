@@ -0,0 +1,235 @@
+use crate::utils::{check_balance, init, FT_TOKEN_ID, NFT_TOKEN_ID};
+use near_sdk_sim::{call, to_yocto, view};
+
+#[test]
+fn simulate_approved_transfer_ft() {
+    let (root, mt, alice, _) = init();
+    call!(
+        root,
+        mt.mt_approve(vec![FT_TOKEN_ID.into()], vec![50.into()], alice.account_id(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+
+    call!(
+        alice,
+        mt.mt_transfer(
+            alice.account_id(),
+            FT_TOKEN_ID.into(),
+            30.into(),
+            Some((root.account_id(), 1)),
+            None
+        ),
+        deposit = 1
+    )
+    .assert_success();
+
+    check_balance(&mt, root.account_id(), FT_TOKEN_ID.to_string(), 70);
+    check_balance(&mt, alice.account_id(), FT_TOKEN_ID.to_string(), 30);
+
+    let still_approved: bool = view!(mt.mt_is_approved(
+        root.account_id(),
+        vec![FT_TOKEN_ID.into()],
+        alice.account_id(),
+        vec![20.into()],
+        Some(vec![1])
+    ))
+    .unwrap_json();
+    assert!(still_approved);
+    let over_approved: bool = view!(mt.mt_is_approved(
+        root.account_id(),
+        vec![FT_TOKEN_ID.into()],
+        alice.account_id(),
+        vec![21.into()],
+        None
+    ))
+    .unwrap_json();
+    assert!(!over_approved);
+}
+
+#[test]
+fn simulate_transfer_exceeding_approval_fails() {
+    let (root, mt, alice, _) = init();
+    call!(
+        root,
+        mt.mt_approve(vec![FT_TOKEN_ID.into()], vec![50.into()], alice.account_id(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+
+    let outcome = call!(
+        alice,
+        mt.mt_transfer(
+            alice.account_id(),
+            FT_TOKEN_ID.into(),
+            60.into(),
+            Some((root.account_id(), 1)),
+            None
+        ),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+    check_balance(&mt, root.account_id(), FT_TOKEN_ID.to_string(), 100);
+}
+
+#[test]
+fn simulate_approved_transfer_nft_clears_approvals() {
+    let (root, mt, alice, _) = init();
+    call!(
+        root,
+        mt.mt_approve(vec![NFT_TOKEN_ID.into()], vec![1.into()], alice.account_id(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+
+    call!(
+        alice,
+        mt.mt_transfer(
+            alice.account_id(),
+            NFT_TOKEN_ID.into(),
+            1.into(),
+            Some((root.account_id(), 1)),
+            None
+        ),
+        deposit = 1
+    )
+    .assert_success();
+
+    check_balance(&mt, alice.account_id(), NFT_TOKEN_ID.to_string(), 1);
+    let approved: bool = view!(mt.mt_is_approved(
+        root.account_id(),
+        vec![NFT_TOKEN_ID.into()],
+        alice.account_id(),
+        vec![1.into()],
+        None
+    ))
+    .unwrap_json();
+    assert!(!approved);
+}
+
+#[test]
+fn simulate_revoked_approval_cannot_transfer() {
+    let (root, mt, alice, _) = init();
+    call!(
+        root,
+        mt.mt_approve(vec![FT_TOKEN_ID.into()], vec![50.into()], alice.account_id(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    call!(root, mt.mt_revoke(vec![FT_TOKEN_ID.into()], alice.account_id()), deposit = 1)
+        .assert_success();
+
+    let outcome = call!(
+        alice,
+        mt.mt_transfer(
+            alice.account_id(),
+            FT_TOKEN_ID.into(),
+            10.into(),
+            Some((root.account_id(), 1)),
+            None
+        ),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+    check_balance(&mt, alice.account_id(), FT_TOKEN_ID.to_string(), 0);
+}
+
+#[test]
+fn simulate_approved_burn() {
+    let (root, mt, alice, _) = init();
+    call!(
+        root,
+        mt.mt_approve(vec![FT_TOKEN_ID.into()], vec![50.into()], alice.account_id(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+
+    call!(alice, mt.mt_burn(FT_TOKEN_ID.into(), 50.into(), Some((root.account_id(), 1)), None), deposit = 1)
+        .assert_success();
+    check_balance(&mt, root.account_id(), FT_TOKEN_ID.to_string(), 50);
+
+    let outcome =
+        call!(alice, mt.mt_burn(FT_TOKEN_ID.into(), 1.into(), Some((root.account_id(), 1)), None), deposit = 1);
+    assert!(!outcome.is_ok());
+}
+
+#[test]
+fn simulate_operator_transfer() {
+    let (root, mt, alice, _) = init();
+    call!(root, mt.mt_set_operator(alice.account_id(), true), deposit = to_yocto("0.01"))
+        .assert_success();
+    let is_operator: bool =
+        view!(mt.mt_is_operator(root.account_id(), alice.account_id())).unwrap_json();
+    assert!(is_operator);
+
+    // Operators name the owner, no approval is needed
+    call!(
+        alice,
+        mt.mt_batch_transfer(
+            alice.account_id(),
+            vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()],
+            vec![20.into(), 1.into()],
+            Some(vec![Some((root.account_id(), 0)), None]),
+            None
+        ),
+        deposit = 1
+    )
+    .assert_success();
+    check_balance(&mt, root.account_id(), FT_TOKEN_ID.to_string(), 80);
+    check_balance(&mt, alice.account_id(), FT_TOKEN_ID.to_string(), 20);
+    check_balance(&mt, alice.account_id(), NFT_TOKEN_ID.to_string(), 1);
+
+    call!(root, mt.mt_set_operator(alice.account_id(), false), deposit = 1).assert_success();
+    let outcome = call!(
+        alice,
+        mt.mt_transfer(
+            alice.account_id(),
+            FT_TOKEN_ID.into(),
+            20.into(),
+            Some((root.account_id(), 0)),
+            None
+        ),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+}
+
+#[test]
+fn simulate_approved_transfer_call_refund_restores_approval() {
+    let (root, mt, alice, receiver) = init();
+    // Storing the spent approval again is paid from the owner's storage balance
+    call!(root, mt.storage_deposit(vec![], None, None), deposit = to_yocto("0.01"))
+        .assert_success();
+    call!(
+        root,
+        mt.mt_approve(vec![FT_TOKEN_ID.into()], vec![50.into()], alice.account_id(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+
+    call!(
+        alice,
+        mt.mt_transfer_call(
+            receiver.account_id(),
+            FT_TOKEN_ID.into(),
+            50.into(),
+            Some((root.account_id(), 1)),
+            None,
+            "return-it-now".into()
+        ),
+        deposit = 1
+    )
+    .assert_success();
+    check_balance(&mt, root.account_id(), FT_TOKEN_ID.to_string(), 100);
+    check_balance(&mt, receiver.account_id(), FT_TOKEN_ID.to_string(), 0);
+
+    let restored: bool = view!(mt.mt_is_approved(
+        root.account_id(),
+        vec![FT_TOKEN_ID.into()],
+        alice.account_id(),
+        vec![50.into()],
+        Some(vec![1])
+    ))
+    .unwrap_json();
+    assert!(restored);
+}
@@ -0,0 +1,70 @@
+use crate::utils::{init, FT_TOKEN_ID, NFT_TOKEN_ID};
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+use near_sdk_sim::{call, to_yocto, view, ContractAccount};
+
+use multi_token::ContractContract as MtContract;
+
+fn balance_at(mt: &ContractAccount<MtContract>, account_id: AccountId, snapshot_id: u64) -> u128 {
+    let balance: U128 =
+        view!(mt.balance_of_at(account_id, FT_TOKEN_ID.into(), snapshot_id)).unwrap_json();
+    balance.0
+}
+
+fn supply_at(mt: &ContractAccount<MtContract>, snapshot_id: u64) -> u128 {
+    let supply: U128 = view!(mt.total_supply_at(FT_TOKEN_ID.into(), snapshot_id)).unwrap_json();
+    supply.0
+}
+
+#[test]
+fn simulate_balance_checkpoints() {
+    let (root, mt, alice, _) = init();
+    let outcome = call!(alice, mt.mt_snapshot(FT_TOKEN_ID.into()), deposit = 1);
+    assert!(!outcome.is_ok());
+    let outcome = call!(root, mt.mt_snapshot(NFT_TOKEN_ID.into()), deposit = to_yocto("0.01"));
+    assert!(!outcome.is_ok());
+
+    let snapshot_id: u64 =
+        call!(root, mt.mt_snapshot(FT_TOKEN_ID.into()), deposit = to_yocto("0.01")).unwrap_json();
+    assert_eq!(snapshot_id, 1);
+
+    // Root has no storage balance to pay for recording its balance at the snapshot
+    let outcome = call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 30.into(), None, None),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+    call!(root, mt.storage_deposit(vec![], None, None), deposit = to_yocto("0.01"))
+        .assert_success();
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 30.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+
+    let snapshot_id: u64 =
+        call!(root, mt.mt_snapshot(FT_TOKEN_ID.into()), deposit = to_yocto("0.01")).unwrap_json();
+    assert_eq!(snapshot_id, 2);
+    call!(
+        alice,
+        mt.mt_transfer(root.account_id(), FT_TOKEN_ID.into(), 10.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    call!(root, mt.mt_burn(FT_TOKEN_ID.into(), 20.into(), None, None), deposit = 1)
+        .assert_success();
+
+    assert_eq!(balance_at(&mt, root.account_id(), 1), 100);
+    assert_eq!(balance_at(&mt, alice.account_id(), 1), 0);
+    assert_eq!(supply_at(&mt, 1), 100);
+    assert_eq!(balance_at(&mt, root.account_id(), 2), 70);
+    assert_eq!(balance_at(&mt, alice.account_id(), 2), 30);
+    assert_eq!(supply_at(&mt, 2), 100);
+
+    let latest: u64 = view!(mt.mt_snapshot_id(FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(latest, 2);
+    let outcome = view!(mt.total_supply_at(FT_TOKEN_ID.into(), 3));
+    assert!(!outcome.is_ok());
+}
@@ -1,4 +1,5 @@
-use crate::utils::{check_balance, init, FT_TOKEN_ID, NFT_TOKEN_ID};
+use crate::utils::{check_balance, get_dummy_metadata, init, FT_TOKEN_ID, NFT_TOKEN_ID};
+use multi_token_standard::{Token, TokenSupply, TokenType};
 use near_sdk::json_types::U128;
 use near_sdk_sim::{call, view, DEFAULT_GAS};
 
@@ -14,6 +15,7 @@ fn simulate_simple_transfer_nft() {
             alice.account_id(),
             NFT_TOKEN_ID.into(),
             1.into(),
+            None,
             Some("simple transfer".to_string())
         ),
         deposit = 1
@@ -28,6 +30,19 @@ fn simulate_simple_transfer_nft() {
     assert_eq!(remaining_amount.0, 0);
 }
 
+#[test]
+fn simulate_transfer_vetoed_by_hook() {
+    let (root, mt, _, _) = init();
+    let outcome = call!(
+        root,
+        mt.mt_transfer(mt.account_id(), NFT_TOKEN_ID.into(), 1.into(), None, None),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+    assert!(format!("{:?}", outcome.status()).contains("can't be transferred to the contract"));
+    check_balance(&mt, root.account_id(), NFT_TOKEN_ID.into(), 1);
+}
+
 #[test]
 fn simulate_simple_transfer_ft() {
     let (root, mt, alice, _) = init();
@@ -40,6 +55,7 @@ fn simulate_simple_transfer_ft() {
             alice.account_id(),
             FT_TOKEN_ID.into(),
             75.into(),
+            None,
             Some("simple transfer".to_string())
         ),
         deposit = 1
@@ -63,6 +79,7 @@ fn simulate_simple_transfer_batch() {
             alice.account_id(),
             vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()],
             vec![75.into(), 1.into()],
+            None,
             Some("simple transfer".to_string())
         ),
         deposit = 1
@@ -82,6 +99,31 @@ fn simulate_simple_transfer_batch() {
     assert_eq!(remaining_amount.0, 0);
 }
 
+#[test]
+fn simulate_balance_of_accounts() {
+    let (root, mt, alice, _) = init();
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 30.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+
+    let balances: Vec<U128> = view!(mt.balance_of_accounts(
+        vec![root.account_id(), alice.account_id()],
+        FT_TOKEN_ID.into()
+    ))
+    .unwrap_json();
+    assert_eq!(balances, vec![U128(70), U128(30)]);
+
+    let matrix: Vec<Vec<U128>> = view!(mt.balance_of_accounts_batch(
+        vec![root.account_id(), alice.account_id()],
+        vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()]
+    ))
+    .unwrap_json();
+    assert_eq!(matrix, vec![vec![U128(70), U128(1)], vec![U128(30), U128(0)]]);
+}
+
 #[test]
 fn simulate_transfer_call_fast_return_to_sender() {
     let (root, mt, _, receiver) = init();
@@ -93,6 +135,7 @@ fn simulate_transfer_call_fast_return_to_sender() {
             receiver.account_id(),
             FT_TOKEN_ID.into(),
             75.into(),
+            None,
             Some("transfer & call".into()),
             "return-it-now".into()
         ),
@@ -119,6 +162,7 @@ fn simulate_batch_transfer_call_fast_return_to_sender() {
         receiver.account_id(),
         vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()],
         vec![75.into(), 1.into()],
+        None,
         Some("transfer & call".into()),
         "return-it-now".into()
     )
@@ -141,6 +185,7 @@ fn simulate_batch_transfer_call_slow_return_to_sender() {
             receiver.account_id(),
             vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()],
             vec![75.into(), 1.into()],
+            None,
             Some("transfer & call".into()),
             "return-it-later".into()
         ),
@@ -165,6 +210,7 @@ fn simulate_batch_transfer_call_fast_keep_with_sender() {
             receiver.account_id(),
             vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()],
             vec![75.into(), 1.into()],
+            None,
             Some("transfer & call".into()),
             "keep-it-now".into()
         ),
@@ -189,6 +235,7 @@ fn simulate_transfer_batch_call_slow_keep_with_sender() {
             receiver.account_id(),
             vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()],
             vec![75.into(), 1.into()],
+            None,
             Some("transfer & call".into()),
             "keep-it-later".into()
         ),
@@ -213,6 +260,7 @@ fn simulate_transfer_call_receiver_panics() {
             receiver.account_id(),
             vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()],
             vec![75.into(), 1.into()],
+            None,
             Some("transfer & call".into()),
             "make-it-panic".into()
         ),
@@ -225,3 +273,90 @@ fn simulate_transfer_call_receiver_panics() {
     check_balance(&mt, root.account_id(), NFT_TOKEN_ID.to_string(), 1);
     check_balance(&mt, receiver.account_id(), NFT_TOKEN_ID.to_string(), 0);
 }
+
+#[test]
+fn simulate_view_tokens() {
+    let (root, mt, _, _) = init();
+    let nft: Option<Token> = view!(mt.mt_token(NFT_TOKEN_ID.into())).unwrap_json();
+    let nft = nft.unwrap();
+    assert_eq!(nft.token_type, TokenType::Nft);
+    assert_eq!(nft.owner_id, Some(root.account_id()));
+    assert_eq!(nft.supply.0, 1);
+    assert!(nft.metadata.is_some());
+
+    let tokens: Vec<Option<Token>> =
+        view!(mt.mt_token_batch(vec![FT_TOKEN_ID.into(), "missing".into()])).unwrap_json();
+    let ft = tokens[0].as_ref().unwrap();
+    assert_eq!(ft.token_type, TokenType::Ft);
+    assert_eq!(ft.owner_id, None);
+    assert_eq!(ft.supply.0, 100);
+    assert!(tokens[1].is_none());
+}
+
+#[test]
+fn simulate_burn() {
+    let (root, mt, _, _) = init();
+    call!(root, mt.mt_burn(FT_TOKEN_ID.into(), 40.into(), None, None), deposit = 1).assert_success();
+    check_balance(&mt, root.account_id(), FT_TOKEN_ID.to_string(), 60);
+    let supply: U128 = view!(mt.total_supply(FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(supply.0, 60);
+
+    call!(root, mt.mt_batch_burn(vec![FT_TOKEN_ID.into()], vec![60.into()], None, None), deposit = 1)
+        .assert_success();
+    let supply: U128 = view!(mt.total_supply(FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(supply.0, 0);
+    let supply: TokenSupply = view!(mt.mt_supply(FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(
+        supply,
+        TokenSupply { minted: 100.into(), burned: 100.into(), circulating: 0.into() }
+    );
+
+    let outcome = call!(root, mt.mt_burn(NFT_TOKEN_ID.into(), 1.into(), None, None), deposit = 1);
+    assert!(!outcome.is_ok());
+}
+
+#[test]
+fn simulate_mint_invalid_metadata() {
+    let (root, mt, _, _) = init();
+    let mut metadata = get_dummy_metadata();
+    metadata.token.reference = Some("/some/uri/reference/{id}_token.json".into());
+    let outcome = call!(
+        root,
+        mt.mint("3".to_string(), TokenType::Ft, Some(10.into()), root.account_id(), Some(metadata)),
+        deposit = 7000000000000000000000
+    );
+    assert!(!outcome.is_ok());
+
+    let mut metadata = get_dummy_metadata();
+    metadata.base.decimals = Some(25);
+    let outcome = call!(
+        root,
+        mt.mint("3".to_string(), TokenType::Ft, Some(10.into()), root.account_id(), Some(metadata)),
+        deposit = 7000000000000000000000
+    );
+    assert!(!outcome.is_ok());
+    let token: Option<Token> = view!(mt.mt_token("3".to_string())).unwrap_json();
+    assert!(token.is_none());
+}
+
+#[test]
+fn simulate_force_unregister_burns_balance() {
+    let (root, mt, alice, _) = init();
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 10.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+
+    let outcome = call!(alice, mt.storage_unregister(vec![FT_TOKEN_ID.into()], None), deposit = 1);
+    assert!(!outcome.is_ok());
+    call!(alice, mt.storage_unregister(vec![FT_TOKEN_ID.into()], Some(true)), deposit = 1)
+        .assert_success();
+
+    let supply: TokenSupply = view!(mt.mt_supply(FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(
+        supply,
+        TokenSupply { minted: 100.into(), burned: 10.into(), circulating: 90.into() }
+    );
+}
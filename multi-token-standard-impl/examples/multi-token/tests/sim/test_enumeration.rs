@@ -0,0 +1,52 @@
+use crate::utils::{init, FT_TOKEN_ID, NFT_TOKEN_ID};
+use multi_token_standard::Token;
+use near_sdk::json_types::U128;
+use near_sdk_sim::{call, view};
+
+#[test]
+fn simulate_enumerate_tokens() {
+    let (root, mt, _, _) = init();
+    let tokens: Vec<Token> = view!(mt.mt_tokens(None, None)).unwrap_json();
+    let token_ids: Vec<String> = tokens.iter().map(|token| token.token_id.clone()).collect();
+    assert_eq!(token_ids, vec![NFT_TOKEN_ID.to_string(), FT_TOKEN_ID.to_string()]);
+    assert_eq!(tokens[0].owner_id, Some(root.account_id()));
+    assert_eq!(tokens[1].owner_id, None);
+    assert_eq!(tokens[1].supply.0, 100);
+
+    let paged: Vec<Token> = view!(mt.mt_tokens(Some(U128::from(1)), Some(1))).unwrap_json();
+    assert_eq!(paged.len(), 1);
+    assert_eq!(paged[0].token_id, FT_TOKEN_ID.to_string());
+}
+
+#[test]
+fn simulate_enumerate_tokens_for_owner() {
+    let (root, mt, alice, _) = init();
+    let supply: U128 = view!(mt.mt_supply_for_owner(root.account_id())).unwrap_json();
+    assert_eq!(supply.0, 2);
+    let alice_tokens: Vec<Token> =
+        view!(mt.mt_tokens_for_owner(alice.account_id(), None, None)).unwrap_json();
+    assert!(alice_tokens.is_empty());
+
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 100.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+
+    let root_tokens: Vec<Token> =
+        view!(mt.mt_tokens_for_owner(root.account_id(), None, None)).unwrap_json();
+    assert_eq!(root_tokens.len(), 1);
+    assert_eq!(root_tokens[0].token_id, NFT_TOKEN_ID.to_string());
+    let alice_tokens: Vec<Token> =
+        view!(mt.mt_tokens_for_owner(alice.account_id(), None, None)).unwrap_json();
+    assert_eq!(alice_tokens.len(), 1);
+    assert_eq!(alice_tokens[0].owner_id, Some(alice.account_id()));
+    let supply: U128 = view!(mt.mt_supply_for_owner(alice.account_id())).unwrap_json();
+    assert_eq!(supply.0, 1);
+
+    // Starting right past the last token gives an empty page, as with `mt_tokens`
+    let alice_tokens: Vec<Token> =
+        view!(mt.mt_tokens_for_owner(alice.account_id(), Some(U128::from(1)), None)).unwrap_json();
+    assert!(alice_tokens.is_empty());
+}
@@ -0,0 +1,68 @@
+use crate::utils::{init, FT_TOKEN_ID, NFT_TOKEN_ID};
+use near_sdk_sim::call;
+
+#[test]
+fn simulate_transfer_emits_event() {
+    let (root, mt, alice, _) = init();
+    let res = call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 75.into(), None, Some("memo".to_string())),
+        deposit = 1
+    );
+    res.assert_success();
+
+    let expected = format!(
+        r#"EVENT_JSON:{{"standard":"nep245","version":"1.0.0","event":"mt_transfer","data":[{{"old_owner_id":"{}","new_owner_id":"{}","token_ids":["{}"],"amounts":["75"],"memo":"memo"}}]}}"#,
+        root.account_id(),
+        alice.account_id(),
+        FT_TOKEN_ID
+    );
+    assert_eq!(res.logs(), &vec![expected]);
+}
+
+#[test]
+fn simulate_batch_transfer_emits_single_event() {
+    let (root, mt, alice, _) = init();
+    let res = call!(
+        root,
+        mt.mt_batch_transfer(
+            alice.account_id(),
+            vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()],
+            vec![75.into(), 1.into()],
+            None,
+            Some("memo".to_string())
+        ),
+        deposit = 1
+    );
+    res.assert_success();
+
+    let expected = format!(
+        r#"EVENT_JSON:{{"standard":"nep245","version":"1.0.0","event":"mt_transfer","data":[{{"old_owner_id":"{}","new_owner_id":"{}","token_ids":["{}","{}"],"amounts":["75","1"],"memo":"memo"}}]}}"#,
+        root.account_id(),
+        alice.account_id(),
+        FT_TOKEN_ID,
+        NFT_TOKEN_ID
+    );
+    assert_eq!(res.logs(), &vec![expected]);
+}
+
+#[test]
+fn simulate_force_unregister_emits_burn_event() {
+    let (root, mt, alice, _) = init();
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 10.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    let res =
+        call!(alice, mt.storage_unregister(vec![FT_TOKEN_ID.into()], Some(true)), deposit = 1);
+    res.assert_success();
+
+    let expected = format!(
+        r#"EVENT_JSON:{{"standard":"nep245","version":"1.0.0","event":"mt_burn","data":[{{"owner_id":"{}","token_ids":["{}"],"amounts":["10"],"memo":"unregister"}}]}}"#,
+        alice.account_id(),
+        FT_TOKEN_ID
+    );
+    assert_eq!(res.logs(), &vec![expected]);
+}
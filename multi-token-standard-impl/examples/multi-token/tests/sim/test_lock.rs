@@ -0,0 +1,102 @@
+use crate::utils::{check_balance, init, FT_TOKEN_ID};
+use multi_token_standard::lock::Lock;
+use near_sdk::json_types::{U128, U64};
+use near_sdk_sim::{call, to_yocto, view};
+
+#[test]
+fn simulate_lock_seize_and_unlock() {
+    let (root, mt, alice, _) = init();
+    call!(
+        root,
+        mt.mt_lock(FT_TOKEN_ID.into(), 60.into(), alice.account_id(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let locked: U128 =
+        view!(mt.mt_locked_balance_of(root.account_id(), FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(locked.0, 60);
+    let locks: Vec<Lock> = view!(mt.mt_locks(root.account_id(), FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(
+        locks,
+        vec![Lock { locker_id: alice.account_id(), amount: 60.into(), expires_at: None }]
+    );
+
+    // Only the unlocked part of the balance can be moved
+    let outcome = call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 50.into(), None, None),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 30.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+
+    // The locker seizes part of the lock
+    call!(
+        alice,
+        mt.mt_seize(FT_TOKEN_ID.into(), root.account_id(), 20.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    check_balance(&mt, root.account_id(), FT_TOKEN_ID.into(), 50);
+    check_balance(&mt, alice.account_id(), FT_TOKEN_ID.into(), 50);
+
+    let outcome = call!(root, mt.mt_burn(FT_TOKEN_ID.into(), 20.into(), None, None), deposit = 1);
+    assert!(!outcome.is_ok());
+    let outcome =
+        call!(root, mt.mt_unlock(FT_TOKEN_ID.into(), root.account_id(), None), deposit = 1);
+    assert!(!outcome.is_ok());
+
+    call!(alice, mt.mt_unlock(FT_TOKEN_ID.into(), root.account_id(), None), deposit = 1)
+        .assert_success();
+    let locked: U128 =
+        view!(mt.mt_locked_balance_of(root.account_id(), FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(locked.0, 0);
+    call!(root, mt.mt_burn(FT_TOKEN_ID.into(), 20.into(), None, None), deposit = 1)
+        .assert_success();
+}
+
+#[test]
+fn simulate_expired_lock_is_removed() {
+    let (root, mt, alice, _) = init();
+    let now = root.borrow_runtime().current_block().block_timestamp;
+    call!(
+        root,
+        mt.mt_lock(
+            FT_TOKEN_ID.into(),
+            60.into(),
+            alice.account_id(),
+            Some(U64::from(now + 5_000_000_000))
+        ),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let outcome = call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 50.into(), None, None),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+
+    root.borrow_runtime_mut().produce_blocks(20).unwrap();
+    let locked: U128 =
+        view!(mt.mt_locked_balance_of(root.account_id(), FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(locked.0, 0);
+    let locks: Vec<Lock> = view!(mt.mt_locks(root.account_id(), FT_TOKEN_ID.into())).unwrap_json();
+    assert_eq!(locks.len(), 1);
+
+    // The next transfer drops the expired lock and refunds its storage
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 50.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    let locks: Vec<Lock> = view!(mt.mt_locks(root.account_id(), FT_TOKEN_ID.into())).unwrap_json();
+    assert!(locks.is_empty());
+    check_balance(&mt, alice.account_id(), FT_TOKEN_ID.into(), 50);
+}
@@ -0,0 +1,72 @@
+use crate::utils::{check_balance, init, FT_TOKEN_ID, NFT_TOKEN_ID};
+use near_sdk_sim::{call, to_yocto, view};
+
+#[test]
+fn simulate_freeze_transfers() {
+    let (root, mt, alice, _) = init();
+    let outcome = call!(alice, mt.mt_freeze_transfers(vec![FT_TOKEN_ID.into()]), deposit = 1);
+    assert!(!outcome.is_ok());
+
+    call!(root, mt.mt_freeze_transfers(vec![FT_TOKEN_ID.into()]), deposit = to_yocto("0.01"))
+        .assert_success();
+    let frozen: bool = view!(mt.mt_is_transfer_frozen(FT_TOKEN_ID.into())).unwrap_json();
+    assert!(frozen);
+    let frozen_ids: Vec<String> = view!(mt.mt_transfer_frozen_token_ids(None, None)).unwrap_json();
+    assert_eq!(frozen_ids, vec![FT_TOKEN_ID.to_string()]);
+
+    let outcome = call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 10.into(), None, None),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+    let outcome = call!(root, mt.mt_burn(FT_TOKEN_ID.into(), 10.into(), None, None), deposit = 1);
+    assert!(!outcome.is_ok());
+    // Other tokens can still be moved
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), NFT_TOKEN_ID.into(), 1.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+
+    call!(root, mt.mt_unfreeze_transfers(vec![FT_TOKEN_ID.into()]), deposit = 1).assert_success();
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 10.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    check_balance(&mt, alice.account_id(), FT_TOKEN_ID.into(), 10);
+}
+
+#[test]
+fn simulate_pause() {
+    let (root, mt, alice, _) = init();
+    let outcome = call!(alice, mt.mt_pause(), deposit = 1);
+    assert!(!outcome.is_ok());
+
+    call!(root, mt.mt_pause(), deposit = 1).assert_success();
+    let paused: bool = view!(mt.mt_is_paused()).unwrap_json();
+    assert!(paused);
+    let outcome = call!(
+        root,
+        mt.mt_batch_transfer(
+            alice.account_id(),
+            vec![FT_TOKEN_ID.into(), NFT_TOKEN_ID.into()],
+            vec![10.into(), 1.into()],
+            None,
+            None
+        ),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+
+    call!(root, mt.mt_unpause(), deposit = 1).assert_success();
+    call!(
+        root,
+        mt.mt_transfer(alice.account_id(), FT_TOKEN_ID.into(), 10.into(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+}
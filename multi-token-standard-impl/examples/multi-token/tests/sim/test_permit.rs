@@ -0,0 +1,74 @@
+use crate::utils::{check_balance, init, FT_TOKEN_ID};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use multi_token_standard::permit::Permit;
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::json_types::{Base64VecU8, U64};
+use near_sdk::AccountId;
+use near_sdk_sim::{call, to_yocto, view};
+use std::convert::TryFrom;
+
+fn keypair(seed: u8) -> Keypair {
+    let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
+fn near_public_key(keypair: &Keypair) -> near_sdk::PublicKey {
+    // Prefixed with the ed25519 curve type
+    near_sdk::PublicKey::try_from([vec![0], keypair.public.to_bytes().to_vec()].concat()).unwrap()
+}
+
+fn sign(keypair: &Keypair, contract_id: AccountId, permit: &Permit) -> Base64VecU8 {
+    let message = (contract_id, permit).try_to_vec().unwrap();
+    keypair.sign(&message).to_bytes().to_vec().into()
+}
+
+#[test]
+fn simulate_transfer_with_permit() {
+    let (root, mt, alice, _) = init();
+    let owner_key = keypair(1);
+    call!(
+        root,
+        mt.mt_set_permit_key(Some(near_public_key(&owner_key))),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+
+    let permit = Permit {
+        owner_id: root.account_id(),
+        receiver_id: alice.account_id(),
+        token_id: FT_TOKEN_ID.into(),
+        amount: 10.into(),
+        nonce: U64(0),
+        deadline: U64(u64::MAX),
+    };
+    // Signed by another key
+    let outcome = call!(
+        alice,
+        mt.mt_transfer_with_permit(
+            permit.clone(),
+            sign(&keypair(2), mt.account_id(), &permit),
+            None
+        )
+    );
+    assert!(!outcome.is_ok());
+
+    // Alice relays the transfer signed by root, paying for the nonce it records
+    let signature = sign(&owner_key, mt.account_id(), &permit);
+    let outcome = call!(alice, mt.mt_transfer_with_permit(permit.clone(), signature.clone(), None));
+    assert!(!outcome.is_ok());
+    call!(
+        alice,
+        mt.mt_transfer_with_permit(permit.clone(), signature.clone(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    check_balance(&mt, root.account_id(), FT_TOKEN_ID.into(), 90);
+    check_balance(&mt, alice.account_id(), FT_TOKEN_ID.into(), 10);
+    let nonce: U64 = view!(mt.mt_permit_nonce(root.account_id())).unwrap_json();
+    assert_eq!(nonce.0, 1);
+
+    // A permit cannot be replayed
+    let outcome = call!(alice, mt.mt_transfer_with_permit(permit, signature, None));
+    assert!(!outcome.is_ok());
+}
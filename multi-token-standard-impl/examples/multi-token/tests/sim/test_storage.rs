@@ -0,0 +1,116 @@
+use crate::utils::{check_balance, init, FT_TOKEN_ID};
+use multi_token_standard::storage_management::{StorageBalance, StorageBalanceBounds};
+use multi_token_standard::TokenType;
+use near_sdk::AccountId;
+use near_sdk_sim::{call, to_yocto, view};
+
+const SECOND_FT_TOKEN_ID: &str = "3";
+
+#[test]
+fn simulate_pooled_storage_deposit() {
+    let (root, mt, _, _) = init();
+    let bob = root.create_user(AccountId::new_unchecked("bob".to_string()), to_yocto("100"));
+    call!(
+        root,
+        mt.mint(
+            SECOND_FT_TOKEN_ID.to_string(),
+            TokenType::Ft,
+            Some(100.into()),
+            root.account_id(),
+            None
+        ),
+        deposit = 7000000000000000000000
+    )
+    .assert_success();
+    let token_ids = vec![FT_TOKEN_ID.to_string(), SECOND_FT_TOKEN_ID.to_string()];
+
+    // Deposit once, without registering for any token
+    let deposit = near_sdk::env::storage_byte_cost() * 2_000;
+    call!(bob, mt.storage_deposit(vec![], None, None), deposit = deposit).assert_success();
+    let pooled: StorageBalance = view!(mt.storage_balance_of(bob.account_id())).unwrap_json();
+    assert_eq!(pooled.total.0, deposit);
+    assert!(pooled.available.0 < deposit);
+
+    // Registering for both tokens draws down the pooled balance
+    let bounds: StorageBalanceBounds =
+        view!(mt.storage_balance_bounds(token_ids.clone(), Some(bob.account_id()))).unwrap_json();
+    call!(bob, mt.storage_deposit(token_ids.clone(), None, None)).assert_success();
+    let registered: StorageBalance = view!(mt.storage_balance_of(bob.account_id())).unwrap_json();
+    assert_eq!(registered.total.0, deposit);
+    assert_eq!(registered.available.0, pooled.available.0 - bounds.min.0);
+    let bounds: StorageBalanceBounds =
+        view!(mt.storage_balance_bounds(token_ids.clone(), Some(bob.account_id()))).unwrap_json();
+    assert_eq!(bounds.min.0, 0);
+
+    for token_id in token_ids.iter() {
+        call!(
+            root,
+            mt.mt_transfer(bob.account_id(), token_id.clone(), 10.into(), None, None),
+            deposit = 1
+        )
+        .assert_success();
+        check_balance(&mt, bob.account_id(), token_id.clone(), 10);
+    }
+
+    // Only the unused remainder can be withdrawn
+    call!(bob, mt.storage_withdraw(None), deposit = 1).assert_success();
+    let withdrawn: StorageBalance = view!(mt.storage_balance_of(bob.account_id())).unwrap_json();
+    assert_eq!(withdrawn.available.0, 0);
+    assert_eq!(withdrawn.total.0, deposit - registered.available.0);
+}
+
+#[test]
+fn simulate_storage_unregister_releases_pooled_balance() {
+    let (root, mt, _, _) = init();
+    let bob = root.create_user(AccountId::new_unchecked("bob".to_string()), to_yocto("100"));
+    call!(
+        bob,
+        mt.storage_deposit(vec![FT_TOKEN_ID.to_string()], None, None),
+        deposit = near_sdk::env::storage_byte_cost() * 2_000
+    )
+    .assert_success();
+    let registered: StorageBalance = view!(mt.storage_balance_of(bob.account_id())).unwrap_json();
+
+    call!(bob, mt.storage_unregister(vec![FT_TOKEN_ID.to_string()], None), deposit = 1)
+        .assert_success();
+    let unregistered: StorageBalance = view!(mt.storage_balance_of(bob.account_id())).unwrap_json();
+    assert_eq!(unregistered.total.0, registered.total.0);
+    assert!(unregistered.available.0 > registered.available.0);
+
+    // Unregistering again is a no-op
+    let res: Vec<bool> =
+        call!(bob, mt.storage_unregister(vec![FT_TOKEN_ID.to_string()], None), deposit = 1)
+            .unwrap_json();
+    assert_eq!(res, vec![false]);
+}
+
+#[test]
+fn simulate_storage_unregister_releases_only_paid_registrations() {
+    let (root, mt, _, _) = init();
+    // root was registered at mint, so its storage balance never paid for the registration
+    call!(
+        root,
+        mt.storage_deposit(vec![], None, None),
+        deposit = near_sdk::env::storage_byte_cost() * 2_000
+    )
+    .assert_success();
+    let deposited: StorageBalance = view!(mt.storage_balance_of(root.account_id())).unwrap_json();
+
+    call!(root, mt.storage_unregister(vec![FT_TOKEN_ID.to_string()], Some(true)), deposit = 1)
+        .assert_success();
+    let unregistered: StorageBalance =
+        view!(mt.storage_balance_of(root.account_id())).unwrap_json();
+    assert_eq!(unregistered.total.0, deposited.total.0);
+    assert_eq!(unregistered.available.0, deposited.available.0);
+}
+
+#[test]
+fn simulate_storage_withdraw_too_much() {
+    let (root, mt, _, _) = init();
+    let bob = root.create_user(AccountId::new_unchecked("bob".to_string()), to_yocto("100"));
+    let deposit = near_sdk::env::storage_byte_cost() * 2_000;
+    call!(bob, mt.storage_deposit(vec![], None, None), deposit = deposit).assert_success();
+
+    let outcome = call!(bob, mt.storage_withdraw(Some(deposit.into())), deposit = 1);
+    assert!(!outcome.is_ok());
+}
@@ -1,5 +1,5 @@
 use multi_token::ContractContract as MtContract;
-use multi_token_standard::metadata::{MultiTokenMetadata, MT_METADATA_SPEC};
+use multi_token_standard::metadata::{BaseTokenMetadata, TokenMetadata, TokenMetadataAll};
 use multi_token_standard::{TokenId, TokenType};
 use near_sdk::json_types::U128;
 use near_sdk::serde_json::json;
@@ -19,13 +19,30 @@ const TOKEN_RECEIVER_ID: &str = "token-receiver";
 pub const NFT_TOKEN_ID: &str = "1";
 pub const FT_TOKEN_ID: &str = "2";
 
+pub fn get_dummy_metadata() -> TokenMetadataAll {
+    TokenMetadataAll {
+        base: BaseTokenMetadata {
+            id: "dummy".to_string(),
+            name: "".to_string(),
+            symbol: None,
+            icon: None,
+            decimals: None,
+            base_uri: None,
+            reference: None,
+            reference_hash: None,
+            copies: None,
+        },
+        token: TokenMetadata::default(),
+    }
+}
+
 pub fn generate_random_token_tuples(
     size: u128,
-) -> (Vec<TokenId>, Vec<TokenType>, Vec<U128>, Vec<Option<MultiTokenMetadata>>) {
+) -> (Vec<TokenId>, Vec<TokenType>, Vec<U128>, Vec<Option<TokenMetadataAll>>) {
     let mut token_types: Vec<TokenType> = vec![];
     let mut amounts: Vec<U128> = vec![];
     let mut token_ids: Vec<TokenId> = vec![];
-    let mut metadatas: Vec<Option<MultiTokenMetadata>> = vec![];
+    let mut metadatas: Vec<Option<TokenMetadataAll>> = vec![];
     let mut counter: u128 = 0;
     for _ in 1..size {
         if rand::random::<bool>() == true {
@@ -37,26 +54,7 @@ pub fn generate_random_token_tuples(
             amounts.push(1.into());
         }
         let metadata = if rand::random::<bool>() == true {
-            Some(MultiTokenMetadata {
-                reference: Some("/some/uri/reference/{id}_token.json".into()),
-                reference_hash: None,
-                title: None,
-                description: None,
-                media: None,
-                media_hash: None,
-                copies: None,
-                issued_at: None,
-                expires_at: None,
-                starts_at: None,
-                updated_at: None,
-                spec: MT_METADATA_SPEC.to_string(),
-                name: "".to_string(),
-                symbol: "".to_string(),
-                icon: None,
-                base_uri: None,
-                decimals: None,
-                extra: None,
-            })
+            Some(get_dummy_metadata())
         } else {
             None
         };
@@ -78,7 +76,7 @@ pub fn register_user(user: &near_sdk_sim::UserAccount, acct_id: AccountId, token
             .to_string()
             .into_bytes(),
         near_sdk_sim::DEFAULT_GAS / 2,
-        near_sdk::env::storage_byte_cost() * 700, // attached deposit
+        near_sdk::env::storage_byte_cost() * 1_000, // attached deposit
     )
         .assert_success();
 }
@@ -108,26 +106,7 @@ pub fn init() -> (UserAccount, ContractAccount<MtContract>, UserAccount, Contrac
     );
 
     let alice = root.create_user(AccountId::new_unchecked("alice".to_string()), to_yocto("100"));
-    let dummy_metadata = MultiTokenMetadata {
-        reference: Some("/some/uri/reference/{id}_token.json".into()),
-        reference_hash: None,
-        title: None,
-        description: None,
-        media: None,
-        media_hash: None,
-        copies: None,
-        issued_at: None,
-        expires_at: None,
-        starts_at: None,
-        updated_at: None,
-        spec: MT_METADATA_SPEC.to_string(),
-        name: "".to_string(),
-        symbol: "".to_string(),
-        icon: None,
-        base_uri: None,
-        decimals: None,
-        extra: None,
-    };
+    let dummy_metadata = get_dummy_metadata();
 
     let token_receiver = deploy!(
         contract: TokenReceiverContract,
@@ -0,0 +1,326 @@
+use crate::approval::{Approval, MultiTokenApproval};
+use crate::token::{TokenId, TokenType};
+use crate::utils::{assert_at_least_one_yocto, refund_deposit};
+use crate::MultiToken;
+use near_sdk::json_types::U128;
+use near_sdk::{
+	assert_one_yocto, env, ext_contract, require, AccountId, Balance, Gas, Promise, PromiseOrValue,
+};
+
+const GAS_FOR_MT_APPROVE: Gas = Gas(10_000_000_000_000);
+const NO_DEPOSIT: Balance = 0;
+
+#[ext_contract(ext_approval_receiver)]
+pub trait MultiTokenApprovalReceiver {
+	fn mt_on_approve(
+		&mut self,
+		token_ids: Vec<TokenId>,
+		amounts: Vec<U128>,
+		owner_id: AccountId,
+		approval_ids: Vec<u64>,
+		msg: String,
+	) -> PromiseOrValue<String>;
+}
+
+impl MultiToken {
+	fn internal_approve(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		owner_id: &AccountId,
+		account_id: &AccountId,
+		amount: Balance,
+	) -> u64 {
+		match self.token_type_index.get(token_id).expect("Token not found") {
+			TokenType::Nft => {
+				let actual_owner = self.nft_owner_by_id.get(token_id).expect("Token not found");
+				require!(&actual_owner == owner_id, "Predecessor must be the token owner");
+				require!(amount == 1, "Approved amount for an NFT type token must be 1");
+			}
+			TokenType::Ft => {
+				let balance = self.internal_unwrap_balance_of(token_id, owner_id);
+				require!(amount <= balance, "Approved amount exceeds balance");
+			}
+		}
+
+		let approvals_by_id = self
+			.approvals_by_id
+			.as_mut()
+			.unwrap_or_else(|| env::panic_str("MultiToken does not support Approval Management"));
+		let next_approval_id_by_id = self.next_approval_id_by_id.as_mut().unwrap();
+
+		let approval_id = next_approval_id_by_id.get(token_id).unwrap_or(1u64);
+		let mut approvals = approvals_by_id.get(token_id).unwrap_or_default();
+		approvals
+			.entry(owner_id.clone())
+			.or_default()
+			.insert(account_id.clone(), Approval { approval_id, amount });
+		approvals_by_id.insert(token_id, &approvals);
+		next_approval_id_by_id.insert(token_id, &(approval_id + 1));
+		approval_id
+	}
+
+	/// Spends `amount` of the allowance `owner_id` granted to `account_id`, panicking if the
+	/// approval does not exist, has a different approval_id or is too small.
+	/// Returns the approval that was spent, with the amount set to the spent amount.
+	pub fn internal_use_approval(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		owner_id: &AccountId,
+		account_id: &AccountId,
+		approval_id: u64,
+		amount: Balance,
+	) -> Approval {
+		let approvals_by_id = self
+			.approvals_by_id
+			.as_mut()
+			.unwrap_or_else(|| env::panic_str("Unauthorized sender must be owner"));
+		let mut approvals = approvals_by_id.get(token_id).unwrap_or_default();
+		let by_owner = approvals
+			.get_mut(owner_id)
+			.unwrap_or_else(|| env::panic_str("Sender not approved"));
+		let approval =
+			by_owner.get_mut(account_id).unwrap_or_else(|| env::panic_str("Sender not approved"));
+		if approval.approval_id != approval_id {
+			env::panic_str(
+				format!(
+					"The actual approval_id {} is different from the given approval_id {}",
+					approval.approval_id, approval_id
+				)
+				.as_str(),
+			);
+		}
+		if approval.amount < amount {
+			env::panic_str("Amount exceeds approved amount");
+		}
+		approval.amount -= amount;
+		if approval.amount == 0 {
+			by_owner.remove(account_id);
+		}
+		if by_owner.is_empty() {
+			approvals.remove(owner_id);
+		}
+		if approvals.is_empty() {
+			approvals_by_id.remove(token_id);
+		} else {
+			approvals_by_id.insert(token_id, &approvals);
+		}
+		Approval { approval_id, amount }
+	}
+
+	/// Gives back `amount` of a spent allowance, used when a transfer is refunded. The allowance
+	/// is only restored if the owner has not re-approved the account in the meantime. Storing a
+	/// fully spent approval again is paid for out of the storage balance of the owner, without
+	/// enough of it the allowance stays spent.
+	pub fn internal_restore_approval(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		owner_id: &AccountId,
+		account_id: &AccountId,
+		approval_id: u64,
+		amount: Balance,
+	) {
+		let approvals_by_id = match self.approvals_by_id.as_mut() {
+			Some(approvals_by_id) => approvals_by_id,
+			None => return,
+		};
+		let initial_storage_usage = env::storage_usage();
+		let previous = approvals_by_id.get(token_id);
+		let mut approvals = previous.clone().unwrap_or_default();
+		let approval = approvals
+			.entry(owner_id.clone())
+			.or_default()
+			.entry(account_id.clone())
+			.or_insert(Approval { approval_id, amount: 0 });
+		if approval.approval_id != approval_id {
+			return;
+		}
+		approval.amount += amount;
+		approvals_by_id.insert(token_id, &approvals);
+
+		let storage_used = env::storage_usage().saturating_sub(initial_storage_usage);
+		if storage_used > 0 && !self.internal_storage_charge(&[owner_id], storage_used) {
+			let approvals_by_id = self.approvals_by_id.as_mut().unwrap();
+			match previous {
+				Some(previous) => approvals_by_id.insert(token_id, &previous),
+				None => approvals_by_id.remove(token_id),
+			};
+		}
+	}
+
+	/// Removes every approval `owner_id` has granted for `token_id`
+	pub fn internal_clear_approvals(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		owner_id: &AccountId,
+	) {
+		if let Some(approvals_by_id) = self.approvals_by_id.as_mut() {
+			if let Some(mut approvals) = approvals_by_id.get(token_id) {
+				approvals.remove(owner_id);
+				if approvals.is_empty() {
+					approvals_by_id.remove(token_id);
+				} else {
+					approvals_by_id.insert(token_id, &approvals);
+				}
+			}
+		}
+	}
+
+	/// Whether `operator_id` was authorized by `owner_id` to transfer any of their tokens
+	pub fn internal_is_operator(&self, owner_id: &AccountId, operator_id: &AccountId) -> bool {
+		self.operators
+			.as_ref()
+			.map(|operators| operators.contains(&(owner_id.clone(), operator_id.clone())))
+			.unwrap_or(false)
+	}
+
+	pub(crate) fn refund_released_storage(account_id: AccountId, initial_storage_usage: u64) {
+		let storage_released = initial_storage_usage.saturating_sub(env::storage_usage());
+		if storage_released > 0 {
+			Promise::new(account_id)
+				.transfer(Balance::from(storage_released) * env::storage_byte_cost());
+		}
+	}
+}
+
+impl MultiTokenApproval for MultiToken {
+	fn mt_approve(
+		&mut self,
+		token_ids: Vec<TokenId>,
+		amounts: Vec<U128>,
+		account_id: AccountId,
+		msg: Option<String>,
+	) -> Option<Promise> {
+		assert_at_least_one_yocto();
+		if token_ids.len() != amounts.len() {
+			env::panic_str("Number of token ids and amounts must be equal")
+		}
+		let owner_id = env::predecessor_account_id();
+		let initial_storage_usage = env::storage_usage();
+
+		let approval_ids: Vec<u64> = token_ids
+			.iter()
+			.zip(amounts.iter())
+			.map(|(token_id, amount)| self.internal_approve(token_id, &owner_id, &account_id, amount.0))
+			.collect();
+
+		refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+		msg.map(|msg| {
+			ext_approval_receiver::mt_on_approve(
+				token_ids,
+				amounts,
+				owner_id,
+				approval_ids,
+				msg,
+				account_id,
+				NO_DEPOSIT,
+				env::prepaid_gas() - GAS_FOR_MT_APPROVE,
+			)
+		})
+	}
+
+	fn mt_revoke(&mut self, token_ids: Vec<TokenId>, account_id: AccountId) {
+		assert_one_yocto();
+		let owner_id = env::predecessor_account_id();
+		let initial_storage_usage = env::storage_usage();
+		let approvals_by_id = self
+			.approvals_by_id
+			.as_mut()
+			.unwrap_or_else(|| env::panic_str("MultiToken does not support Approval Management"));
+		for token_id in token_ids.iter() {
+			let mut approvals = match approvals_by_id.get(token_id) {
+				Some(approvals) => approvals,
+				None => continue,
+			};
+			if let Some(by_owner) = approvals.get_mut(&owner_id) {
+				if by_owner.remove(&account_id).is_none() {
+					continue;
+				}
+				if by_owner.is_empty() {
+					approvals.remove(&owner_id);
+				}
+				if approvals.is_empty() {
+					approvals_by_id.remove(token_id);
+				} else {
+					approvals_by_id.insert(token_id, &approvals);
+				}
+			}
+		}
+		Self::refund_released_storage(owner_id, initial_storage_usage);
+	}
+
+	fn mt_revoke_all(&mut self, token_ids: Vec<TokenId>) {
+		assert_one_yocto();
+		if self.approvals_by_id.is_none() {
+			env::panic_str("MultiToken does not support Approval Management");
+		}
+		let owner_id = env::predecessor_account_id();
+		let initial_storage_usage = env::storage_usage();
+		for token_id in token_ids.iter() {
+			self.internal_clear_approvals(token_id, &owner_id);
+		}
+		Self::refund_released_storage(owner_id, initial_storage_usage);
+	}
+
+	fn mt_is_approved(
+		&self,
+		owner_id: AccountId,
+		token_ids: Vec<TokenId>,
+		approved_account_id: AccountId,
+		amounts: Vec<U128>,
+		approval_ids: Option<Vec<u64>>,
+	) -> bool {
+		if token_ids.len() != amounts.len() {
+			env::panic_str("Number of token ids and amounts must be equal")
+		}
+		if let Some(approval_ids) = approval_ids.as_ref() {
+			if token_ids.len() != approval_ids.len() {
+				env::panic_str("Number of token ids and approval ids must be equal")
+			}
+		}
+		let approvals_by_id = if let Some(approvals_by_id) = self.approvals_by_id.as_ref() {
+			approvals_by_id
+		} else {
+			return false;
+		};
+		token_ids.iter().enumerate().all(|(idx, token_id)| {
+			let approval = approvals_by_id
+				.get(token_id)
+				.and_then(|approvals| approvals.get(&owner_id).cloned())
+				.and_then(|by_owner| by_owner.get(&approved_account_id).cloned());
+			match approval {
+				Some(approval) => {
+					approval.amount >= amounts[idx].0
+						&& approval_ids
+							.as_ref()
+							.map(|approval_ids| approval_ids[idx] == approval.approval_id)
+							.unwrap_or(true)
+				}
+				None => false,
+			}
+		})
+	}
+
+	fn mt_set_operator(&mut self, operator_id: AccountId, approved: bool) {
+		let owner_id = env::predecessor_account_id();
+		require!(owner_id != operator_id, "The owner cannot be its own operator");
+		let initial_storage_usage = env::storage_usage();
+		let operators = self
+			.operators
+			.as_mut()
+			.unwrap_or_else(|| env::panic_str("MultiToken does not support Approval Management"));
+		if approved {
+			assert_at_least_one_yocto();
+			operators.insert(&(owner_id, operator_id));
+			refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+		} else {
+			assert_one_yocto();
+			operators.remove(&(owner_id.clone(), operator_id));
+			Self::refund_released_storage(owner_id, initial_storage_usage);
+		}
+	}
+
+	fn mt_is_operator(&self, owner_id: AccountId, operator_id: AccountId) -> bool {
+		self.internal_is_operator(&owner_id, &operator_id)
+	}
+}
@@ -0,0 +1,30 @@
+use crate::token::TokenId;
+use near_sdk::json_types::U128;
+use near_sdk::{AccountId, PromiseOrValue};
+
+/// Approval receiver is the trait for the method called (or attempted to be called) when a
+/// MultiToken contract adds an approval for an account.
+pub trait MultiTokenApprovalReceiver {
+    /// Respond to notification that contract has been granted approval for tokens.
+    ///
+    /// Notes
+    /// * Contract knows the token contract ID from `predecessor_account_id`
+    ///
+    /// Arguments:
+    /// * `token_ids`: the token ids to which this contract has been granted approval
+    /// * `amounts`: the approved amount for corresponding token_id
+    /// * `owner_id`: the owner of the tokens
+    /// * `approval_ids`: the approval ID stored by the MultiToken contract for this approval.
+    ///    Expected to be a number within the 2^53 limit representable by JSON.
+    /// * `msg`: specifies information needed by the approved contract in order to
+    ///    handle the approval. Can indicate both a function to call and the
+    ///    parameters to pass to that function.
+    fn mt_on_approve(
+        &mut self,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        owner_id: AccountId,
+        approval_ids: Vec<u64>,
+        msg: String,
+    ) -> PromiseOrValue<String>;
+}
@@ -0,0 +1,121 @@
+mod approval_impl;
+mod approval_receiver;
+
+pub use self::approval_impl::*;
+pub use self::approval_receiver::*;
+
+use crate::token::TokenId;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::{AccountId, Balance, Promise};
+
+/// A single allowance granted by a token owner to another account. For NFT type tokens the
+/// amount is always 1.
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct Approval {
+    pub approval_id: u64,
+    pub amount: Balance,
+}
+
+/// Trait used when it's desired to have a multi token that has a
+/// traditional escrow or approval system. This allows Alice to allow Bob
+/// to take only a certain amount of her tokens, and do so per token id.
+///
+/// Approvals are tracked per token id and per owner, since fungible type token ids can have
+/// many owners at once.
+pub trait MultiTokenApproval {
+    /// Add an approved account for specific token ids and amounts.
+    ///
+    /// Requirements
+    /// * Caller of the method must attach a deposit of at least 1 yoctoⓃ for
+    ///   security purposes
+    /// * Contract MAY require caller to attach larger deposit, to cover cost of
+    ///   storing approver data
+    /// * Contract MUST panic if called by someone other than token owner
+    /// * Contract MUST panic if addition would cause `mt_revoke_all` to exceed
+    ///   single-block gas limit
+    /// * Contract MUST increment approval ID even if re-approving an account
+    /// * If successfully approved or if had already been approved, and if `msg` is
+    ///   present, contract MUST call `mt_on_approve` on `account_id`. See
+    ///   `mt_on_approve` description below for details.
+    ///
+    /// Arguments:
+    /// * `token_ids`: the token ids for which to add an approval
+    /// * `amounts`: the number of tokens to approve for corresponding token_id
+    /// * `account_id`: the account to add to the approvals
+    /// * `msg`: optional string to be passed to `mt_on_approve`
+    ///
+    /// Returns void, if no `msg` given. Otherwise, returns promise call to
+    /// `mt_on_approve`, which can resolve with whatever it wants.
+    fn mt_approve(
+        &mut self,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        account_id: AccountId,
+        msg: Option<String>,
+    ) -> Option<Promise>;
+
+    /// Revoke an approved account for specific token ids.
+    ///
+    /// Requirements
+    /// * Caller of the method must attach a deposit of 1 yoctoⓃ for security
+    ///   purposes
+    /// * If contract requires >1yN deposit on `mt_approve`, contract
+    ///   MUST refund associated storage deposit when owner revokes approval
+    /// * Contract MUST panic if called by someone other than token owner
+    ///
+    /// Arguments:
+    /// * `token_ids`: the token ids for which to revoke the approval
+    /// * `account_id`: the account to remove from the approvals
+    fn mt_revoke(&mut self, token_ids: Vec<TokenId>, account_id: AccountId);
+
+    /// Revoke all approved accounts for specific token ids.
+    ///
+    /// Requirements
+    /// * Caller of the method must attach a deposit of 1 yoctoⓃ for security
+    ///   purposes
+    /// * If contract requires >1yN deposit on `mt_approve`, contract
+    ///   MUST refund all associated storage deposit when owner revokes approvals
+    /// * Contract MUST panic if called by someone other than token owner
+    ///
+    /// Arguments:
+    /// * `token_ids`: the token ids with approvals to revoke
+    fn mt_revoke_all(&mut self, token_ids: Vec<TokenId>);
+
+    /// Check if tokens of `owner_id` are approved for transfer by a given account, optionally
+    /// checking an approval_id
+    ///
+    /// Arguments:
+    /// * `owner_id`: the owner who granted the approvals
+    /// * `token_ids`: the tokens for which to check an approval
+    /// * `approved_account_id`: the account to check the existence of in approvals
+    /// * `amounts`: the amounts that must still be approved for corresponding token_id
+    /// * `approval_ids`: optional approval IDs to check against current approval IDs
+    ///    for given account and `token_ids`.
+    ///
+    /// Returns:
+    /// if `approval_ids` is given, `true` if `approved_account_id` is approved with given
+    /// `approval_ids` and at least `amounts` for every token, otherwise `true` if
+    /// `approved_account_id` is approved for at least `amounts` for every token.
+    fn mt_is_approved(
+        &self,
+        owner_id: AccountId,
+        token_ids: Vec<TokenId>,
+        approved_account_id: AccountId,
+        amounts: Vec<U128>,
+        approval_ids: Option<Vec<u64>>,
+    ) -> bool;
+
+    /// Authorizes `operator_id` to transfer any token the caller holds on this contract, without
+    /// per token approvals, or withdraws the authorization when `approved` is false. Operators
+    /// name the owner in the `approval` of a transfer, the approval_id is not checked for them.
+    ///
+    /// Requirements
+    /// * Caller of the method must attach a deposit of at least 1 yoctoⓃ when approving, covering
+    ///   the storage used, and of exactly 1 yoctoⓃ when withdrawing
+    /// * Contract MUST refund the storage deposit when the authorization is withdrawn
+    fn mt_set_operator(&mut self, operator_id: AccountId, approved: bool);
+
+    /// Whether `operator_id` may transfer any token of `owner_id`
+    fn mt_is_operator(&self, owner_id: AccountId, operator_id: AccountId) -> bool;
+}
@@ -0,0 +1,169 @@
+use crate::checkpoint::MultiTokenCheckpoint;
+use crate::token::{TokenId, TokenType};
+use crate::utils::{assert_at_least_one_yocto, refund_deposit};
+use crate::MultiToken;
+use near_sdk::json_types::U128;
+use near_sdk::{env, require, AccountId, Balance, StorageUsage};
+
+impl MultiToken {
+	/// Takes a new snapshot of the FT type `token_id` and returns its id. The supply at the snapshot
+	/// is recorded right away, so its storage is paid for along with the snapshot.
+	pub fn internal_snapshot(&mut self, #[allow(clippy::ptr_arg)] token_id: &TokenId) -> u64 {
+		require!(
+			self.token_type_index.get(token_id) == Some(TokenType::Ft),
+			"Only FT type tokens can have snapshots"
+		);
+		let snapshot_id_by_id = self
+			.snapshot_id_by_id
+			.as_mut()
+			.unwrap_or_else(|| env::panic_str("MultiToken does not support Checkpoints"));
+		let snapshot_id = snapshot_id_by_id.get(token_id).unwrap_or(0) + 1;
+		snapshot_id_by_id.insert(token_id, &snapshot_id);
+		let supply = self.ft_token_supply_by_id.get(token_id).unwrap_or(0);
+		self.supply_checkpoints.as_mut().unwrap().insert(&(token_id.clone(), snapshot_id), &supply);
+		snapshot_id
+	}
+
+	/// The id of the latest snapshot of `token_id`, 0 if none was taken
+	pub fn internal_snapshot_id(&self, #[allow(clippy::ptr_arg)] token_id: &TokenId) -> u64 {
+		self.snapshot_id_by_id.as_ref().and_then(|ids| ids.get(token_id)).unwrap_or(0)
+	}
+
+	/// Records the balance `account_id` holds of `token_id` at its latest snapshot. Must be called
+	/// before the balance changes. The storage is paid for out of the storage balance of
+	/// `account_id`, or of the predecessor when that one is too low.
+	pub fn internal_checkpoint_balance(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+	) {
+		if let Some(storage_used) = self.internal_record_balance(token_id, account_id) {
+			let predecessor_id = env::predecessor_account_id();
+			if !self.internal_storage_charge(&[account_id, &predecessor_id], storage_used) {
+				env::panic_str(
+					format!(
+						"The storage balance of {} or {} is too low to record the balance at the snapshot of {}",
+						account_id, predecessor_id, token_id
+					)
+					.as_str(),
+				);
+			}
+		}
+	}
+
+	/// Same as [`internal_checkpoint_balance`](Self::internal_checkpoint_balance) for refunds in
+	/// `mt_resolve_transfer`, paid for by `account_id` or else `sender_id`. A refund can't fail,
+	/// so the contract covers the record when neither can pay. That only happens for a snapshot
+	/// taken while the transfer call was pending, once per account and snapshot.
+	pub fn internal_checkpoint_balance_on_resolve(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+		sender_id: &AccountId,
+	) {
+		if let Some(storage_used) = self.internal_record_balance(token_id, account_id) {
+			self.internal_storage_charge(&[account_id, sender_id], storage_used);
+		}
+	}
+
+	/// Records the balance `account_id` holds of `token_id` at its latest snapshot unless it already
+	/// was, returning the storage used
+	fn internal_record_balance(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+	) -> Option<StorageUsage> {
+		let snapshot_id = self.internal_snapshot_id(token_id);
+		if snapshot_id == 0 {
+			return None;
+		}
+		let balance = self
+			.ft_owners_by_id
+			.get(token_id)
+			.and_then(|balances| balances.get(account_id))
+			.unwrap_or(0);
+		let balance_checkpoints = self.balance_checkpoints.as_mut().unwrap();
+		let key = (token_id.clone(), account_id.clone());
+		let mut checkpoints = balance_checkpoints.get(&key).unwrap_or_default();
+		if !push_checkpoint(&mut checkpoints, snapshot_id, balance) {
+			return None;
+		}
+		let initial_storage_usage = env::storage_usage();
+		balance_checkpoints.insert(&key, &checkpoints);
+		Some(env::storage_usage() - initial_storage_usage)
+	}
+
+	fn assert_snapshot(&self, #[allow(clippy::ptr_arg)] token_id: &TokenId, snapshot_id: u64) {
+		if snapshot_id == 0 || snapshot_id > self.internal_snapshot_id(token_id) {
+			env::panic_str(
+				format!("Snapshot {} of {} does not exist", snapshot_id, token_id).as_str(),
+			);
+		}
+	}
+}
+
+/// Adds the value at `snapshot_id` unless one was already recorded since that snapshot was taken
+fn push_checkpoint(
+	checkpoints: &mut Vec<(u64, Balance)>,
+	snapshot_id: u64,
+	value: Balance,
+) -> bool {
+	if checkpoints.last().map(|(id, _)| *id < snapshot_id).unwrap_or(true) {
+		checkpoints.push((snapshot_id, value));
+		true
+	} else {
+		false
+	}
+}
+
+/// The first checkpoint recorded at or after `snapshot_id` holds the value at that snapshot,
+/// without one the value has not changed since
+fn value_at(
+	checkpoints: Option<Vec<(u64, Balance)>>,
+	snapshot_id: u64,
+	current: Balance,
+) -> Balance {
+	checkpoints
+		.and_then(|checkpoints| {
+			let idx = checkpoints.partition_point(|(id, _)| *id < snapshot_id);
+			checkpoints.get(idx).map(|(_, value)| *value)
+		})
+		.unwrap_or(current)
+}
+
+impl MultiTokenCheckpoint for MultiToken {
+	fn mt_snapshot(&mut self, token_id: TokenId) -> u64 {
+		assert_at_least_one_yocto();
+		require!(
+			env::predecessor_account_id() == self.owner_id,
+			"Only the owner can take snapshots"
+		);
+		let initial_storage_usage = env::storage_usage();
+		let snapshot_id = self.internal_snapshot(&token_id);
+		refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+		snapshot_id
+	}
+
+	fn mt_snapshot_id(&self, token_id: TokenId) -> u64 {
+		self.internal_snapshot_id(&token_id)
+	}
+
+	fn balance_of_at(&self, account_id: AccountId, token_id: TokenId, snapshot_id: u64) -> U128 {
+		self.assert_snapshot(&token_id, snapshot_id);
+		let current = self
+			.ft_owners_by_id
+			.get(&token_id)
+			.and_then(|balances| balances.get(&account_id))
+			.unwrap_or(0);
+		let checkpoints = self
+			.balance_checkpoints
+			.as_ref()
+			.and_then(|checkpoints| checkpoints.get(&(token_id, account_id)));
+		value_at(checkpoints, snapshot_id, current).into()
+	}
+
+	fn total_supply_at(&self, token_id: TokenId, snapshot_id: u64) -> U128 {
+		self.assert_snapshot(&token_id, snapshot_id);
+		self.supply_checkpoints.as_ref().unwrap().get(&(token_id, snapshot_id)).unwrap().into()
+	}
+}
@@ -0,0 +1,32 @@
+mod checkpoint_impl;
+
+pub use self::checkpoint_impl::*;
+
+use crate::token::TokenId;
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+
+/// Balances of FT type tokens at a point in time, so that votes and payouts based on holdings
+/// cannot be gamed by moving tokens around while they are counted.
+///
+/// A snapshot of a token is taken by the owner of the contract, or by the contract itself through
+/// [`MultiToken::internal_snapshot`](crate::MultiToken::internal_snapshot). Snapshot ids start
+/// at 1 and increase per token id. Only tokens which had a snapshot taken keep checkpoints, every
+/// later balance change records the value it had at the latest snapshot. The supply is recorded
+/// when the snapshot is taken, paid for by its caller. A balance is recorded by the first change
+/// to it after a snapshot, paid for out of the storage balance of its holder, or of the caller
+/// when the holder's is too low. Holders deposit it with `storage_deposit`.
+pub trait MultiTokenCheckpoint {
+    /// Takes a new snapshot of the FT type `token_id` and returns its id. Only callable by the
+    /// owner of the contract, which must attach enough deposit to cover the storage used.
+    fn mt_snapshot(&mut self, token_id: TokenId) -> u64;
+
+    /// The id of the latest snapshot of `token_id`, 0 if none was taken
+    fn mt_snapshot_id(&self, token_id: TokenId) -> u64;
+
+    /// The balance `account_id` held of `token_id` when snapshot `snapshot_id` was taken
+    fn balance_of_at(&self, account_id: AccountId, token_id: TokenId, snapshot_id: u64) -> U128;
+
+    /// The total supply of `token_id` when snapshot `snapshot_id` was taken
+    fn total_supply_at(&self, token_id: TokenId, snapshot_id: u64) -> U128;
+}
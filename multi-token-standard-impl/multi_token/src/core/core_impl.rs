@@ -1,14 +1,19 @@
+use crate::approval::Approval;
 use crate::core::MultiTokenCore;
 use crate::core::resolver::MultiTokenResolver;
-use crate::metadata::{MultiTokenMetadata, MT_METADATA_SPEC};
-use crate::token::{TokenId, TokenType};
-use crate::utils::refund_deposit;
+use crate::events::{MtBurn, MtMint, MtTransfer};
+use crate::lock::Lock;
+use crate::metadata::{BaseTokenMetadata, TokenMetadata, TokenMetadataAll};
+use crate::storage_management::StorageBalance;
+use crate::token::{Token, TokenId, TokenSupply, TokenType};
+use crate::utils::{assert_at_least_one_yocto, refund_deposit};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LookupMap};
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedSet};
 use near_sdk::json_types::{U128};
+use std::collections::HashMap;
 use near_sdk::{
-	assert_one_yocto, env, ext_contract, log, AccountId, Balance, Gas, IntoStorageKey,
-	PromiseOrValue, PromiseResult, StorageUsage,
+	assert_one_yocto, env, ext_contract, log, require, AccountId, Balance, Gas, IntoStorageKey,
+	PromiseOrValue, PromiseResult, PublicKey, StorageUsage,
 };
 
 const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(5_000_000_000_000);
@@ -20,10 +25,11 @@ const NO_DEPOSIT: Balance = 0;
 trait MultiTokenResolver {
 	fn mt_resolve_transfer(
 		&mut self,
-		sender_id: AccountId,
+		previous_owner_ids: Vec<AccountId>,
 		receiver_id: AccountId,
 		token_ids: Vec<TokenId>,
 		amounts: Vec<U128>,
+		approvals: Option<Vec<Option<(AccountId, u64, U128)>>>,
 	) -> Vec<U128>;
 }
 
@@ -33,6 +39,7 @@ pub trait MultiTokenReceiver {
 	fn mt_on_transfer(
 		&mut self,
 		sender_id: AccountId,
+		previous_owner_ids: Vec<AccountId>,
 		token_ids: Vec<TokenId>,
 		amounts: Vec<U128>,
 		msg: String,
@@ -43,7 +50,7 @@ pub trait MultiTokenReceiver {
 /// There are next traits that any contract may implement:
 ///     - MultiTokenCore -- interface with mt_transfer/balance/supply methods. MultiToken provides methods for it.
 ///     - MultiTokenApproval -- interface with mt_approve methods. MultiToken provides methods for it.
-///     - MultiTokenMetadata -- return metadata for the token in NEP-177, up to contract to implement.
+///     - MultiTokenMetadataProvider -- return contract, base and token metadata. MultiToken provides methods for the token views.
 ///
 /// For example usage, see examples/non-fungible-token/src/lib.rs.
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -70,25 +77,129 @@ pub struct MultiToken {
 
 	// always required mapping to token supply
 	pub ft_token_supply_by_id: LookupMap<TokenId, u128>,
+	// amount of each token taken out of circulation
+	pub ft_burned_by_id: LookupMap<TokenId, u128>,
+
+	// storage deposited by each account, drawn down as it registers for FT type tokens
+	pub storage_balances: LookupMap<AccountId, StorageBalance>,
+	// storage balance each registration drew from its account, given back when it unregisters
+	pub storage_paid_registrations: LookupMap<(TokenId, AccountId), Balance>,
+	// opt-in, lets `mt_transfer` and `mt_batch_transfer` register receivers of FT type tokens
+	pub auto_register_receivers: bool,
+
+	// balances of FT type tokens locked by their holders for other accounts
+	pub ft_locks: LookupMap<(TokenId, AccountId), Vec<Lock>>,
+
+	// ed25519 keys accounts sign transfer permits with, and the nonce of their next permit
+	pub permit_keys: LookupMap<AccountId, PublicKey>,
+	pub permit_nonces: LookupMap<AccountId, u64>,
+
+	// token ids which cannot be moved, and whether no token can be moved at all
+	pub transfer_frozen_token_ids: UnorderedSet<TokenId>,
+	pub paused: bool,
+
+	// required by metadata extension, base metadata is stored once and referenced by id per token
+	pub token_metadata_by_id: Option<LookupMap<TokenId, TokenMetadata>>,
+	pub base_metadata_by_id: Option<LookupMap<String, BaseTokenMetadata>>,
+	pub base_metadata_id_by_token_id: Option<LookupMap<TokenId, String>>,
+	pub metadata_creator_by_id: Option<LookupMap<TokenId, AccountId>>,
+	pub frozen_metadata: Option<LookupSet<TokenId>>,
+
+	// required by approval extension, approvals are kept per token id and per owner
+	pub approvals_by_id: Option<LookupMap<TokenId, HashMap<AccountId, HashMap<AccountId, Approval>>>>,
+	pub next_approval_id_by_id: Option<LookupMap<TokenId, u64>>,
+	// (owner, operator) pairs, operators may transfer any token of the owner
+	pub operators: Option<LookupSet<(AccountId, AccountId)>>,
 
-	// required by metadata extension
-	pub token_metadata_by_id: Option<LookupMap<TokenId, MultiTokenMetadata>>,
+	// required by enumeration extension
+	pub all_token_ids: Option<UnorderedSet<TokenId>>,
+	pub tokens_per_owner: Option<LookupMap<AccountId, UnorderedSet<TokenId>>>,
+
+	// required by checkpoint extension, values of FT type tokens at the snapshots taken of them
+	pub snapshot_id_by_id: Option<LookupMap<TokenId, u64>>,
+	pub balance_checkpoints: Option<LookupMap<(TokenId, AccountId), Vec<(u64, Balance)>>>,
+	pub supply_checkpoints: Option<LookupMap<(TokenId, u64), Balance>>,
+
+	// required by payout extension, royalties in basis points of the sale price
+	pub royalties_by_id: Option<LookupMap<TokenId, HashMap<AccountId, u32>>>,
 }
 
 impl MultiToken {
-	pub fn new<Q, R, T>(
+	pub fn new<Q, R, T, A, E, C, P>(
 		owner_by_id_prefix: Q,
 		owner_id: AccountId,
 		token_metadata_prefix: Option<R>,
 		supply_by_id_prefix: T,
+		approval_prefix: Option<A>,
+		enumeration_prefix: Option<E>,
+		checkpoint_prefix: Option<C>,
+		payout_prefix: Option<P>,
 	) -> Self
 	where
 		Q: IntoStorageKey,
 		R: IntoStorageKey,
 		T: IntoStorageKey,
+		A: IntoStorageKey,
+		E: IntoStorageKey,
+		C: IntoStorageKey,
+		P: IntoStorageKey,
 	{
 		let owner_prefix: Vec<u8> = owner_by_id_prefix.into_storage_key();
 		let token_type_prefix = [owner_prefix.clone(), "t".into()].concat();
+		let storage_balances_prefix = [owner_prefix.clone(), "s".into()].concat();
+		let storage_paid_prefix = [owner_prefix.clone(), "r".into()].concat();
+		let burned_prefix = [owner_prefix.clone(), "b".into()].concat();
+		let transfer_frozen_prefix = [owner_prefix.clone(), "f".into()].concat();
+		let locks_prefix = [owner_prefix.clone(), "l".into()].concat();
+		let permit_keys_prefix = [owner_prefix.clone(), "k".into()].concat();
+		let permit_nonces_prefix = [owner_prefix.clone(), "c".into()].concat();
+		let (
+			token_metadata_by_id,
+			base_metadata_by_id,
+			base_metadata_id_by_token_id,
+			metadata_creator_by_id,
+			frozen_metadata,
+		) = if let Some(prefix) = token_metadata_prefix {
+			let prefix: Vec<u8> = prefix.into_storage_key();
+			(
+				Some(LookupMap::new(prefix.clone())),
+				Some(LookupMap::new([prefix.clone(), "b".into()].concat())),
+				Some(LookupMap::new([prefix.clone(), "r".into()].concat())),
+				Some(LookupMap::new([prefix.clone(), "c".into()].concat())),
+				Some(LookupSet::new([prefix, "f".into()].concat())),
+			)
+		} else {
+			(None, None, None, None, None)
+		};
+		let (approvals_by_id, next_approval_id_by_id, operators) =
+			if let Some(prefix) = approval_prefix {
+				let prefix: Vec<u8> = prefix.into_storage_key();
+				(
+					Some(LookupMap::new(prefix.clone())),
+					Some(LookupMap::new([prefix.clone(), "n".into()].concat())),
+					Some(LookupSet::new([prefix, "p".into()].concat())),
+				)
+			} else {
+				(None, None, None)
+			};
+		let (all_token_ids, tokens_per_owner) = if let Some(prefix) = enumeration_prefix {
+			let prefix: Vec<u8> = prefix.into_storage_key();
+			(Some(UnorderedSet::new(prefix.clone())), Some(LookupMap::new([prefix, "o".into()].concat())))
+		} else {
+			(None, None)
+		};
+		let (snapshot_id_by_id, balance_checkpoints, supply_checkpoints) =
+			if let Some(prefix) = checkpoint_prefix {
+				let prefix: Vec<u8> = prefix.into_storage_key();
+				(
+					Some(LookupMap::new(prefix.clone())),
+					Some(LookupMap::new([prefix.clone(), "a".into()].concat())),
+					Some(LookupMap::new([prefix, "s".into()].concat())),
+				)
+			} else {
+				(None, None, None)
+			};
+		let royalties_by_id = payout_prefix.map(LookupMap::new);
 		let mut this = Self {
 			owner_id,
 			owner_prefix: owner_prefix.clone(),
@@ -100,7 +211,29 @@ impl MultiToken {
 			token_type_index: LookupMap::new(token_type_prefix.into_storage_key()),
 			ft_prefix_index: 0,
 			ft_token_supply_by_id: LookupMap::new(supply_by_id_prefix.into_storage_key()),
-			token_metadata_by_id: token_metadata_prefix.map(LookupMap::new),
+			ft_burned_by_id: LookupMap::new(burned_prefix),
+			storage_balances: LookupMap::new(storage_balances_prefix),
+			storage_paid_registrations: LookupMap::new(storage_paid_prefix),
+			auto_register_receivers: false,
+			ft_locks: LookupMap::new(locks_prefix),
+			permit_keys: LookupMap::new(permit_keys_prefix),
+			permit_nonces: LookupMap::new(permit_nonces_prefix),
+			transfer_frozen_token_ids: UnorderedSet::new(transfer_frozen_prefix),
+			paused: false,
+			token_metadata_by_id,
+			base_metadata_by_id,
+			base_metadata_id_by_token_id,
+			metadata_creator_by_id,
+			frozen_metadata,
+			approvals_by_id,
+			next_approval_id_by_id,
+			operators,
+			all_token_ids,
+			tokens_per_owner,
+			snapshot_id_by_id,
+			balance_checkpoints,
+			supply_checkpoints,
+			royalties_by_id,
 		};
 		this.measure_min_ft_token_storage_cost();
 		this.measure_min_nft_token_storage_cost();
@@ -133,11 +266,17 @@ impl MultiToken {
 		let tmp_supply: u128 = 9999;
 		tmp_balance_lookup.insert(&tmp_owner_id, &tmp_supply);
 		self.ft_owners_by_id.insert(&tmp_token_id, &tmp_balance_lookup);
+		self.internal_enumeration_add(&tmp_token_id, &tmp_owner_id);
+		self.storage_paid_registrations
+			.insert(&(tmp_token_id.clone(), tmp_owner_id.clone()), &tmp_supply);
 
 		// 2. measure the space taken up
 		self.ft_account_storage_usage = env::storage_usage() - storage_after_token_creation;
 
 		// 3. roll it all back
+		self.storage_paid_registrations.remove(&(tmp_token_id.clone(), tmp_owner_id.clone()));
+		self.internal_enumeration_remove(&tmp_token_id, &tmp_owner_id);
+		tmp_balance_lookup.remove(&tmp_owner_id);
 		self.ft_owners_by_id.remove(&tmp_token_id);
 	}
 
@@ -148,37 +287,28 @@ impl MultiToken {
 		let tmp_owner_id = AccountId::new_unchecked("a".repeat(64));
 
 		self.nft_owner_by_id.insert(&tmp_token_id, &tmp_owner_id);
-		if let Some(token_metadata_by_id) = &mut self.token_metadata_by_id {
-			token_metadata_by_id.insert(
-				&tmp_token_id,
-				&MultiTokenMetadata {
-					name: "a".repeat(64),
-					symbol: "a".repeat(64),
-					spec: MT_METADATA_SPEC.to_string(),
-					icon: None,
-					base_uri: None,
-					decimals: None,
-					reference: None,
-					reference_hash: None,
-					title: None,
-					description: None,
-					media: None,
-					media_hash: None,
-					copies: None,
-					issued_at: None,
-					expires_at: None,
-					starts_at: None,
-					updated_at: None,
-					extra: None
-				},
-			);
+		// the base metadata is shared between tokens, so only the reference to it is measured
+		if let (Some(token_metadata_by_id), Some(base_metadata_id_by_token_id)) =
+			(&mut self.token_metadata_by_id, &mut self.base_metadata_id_by_token_id)
+		{
+			token_metadata_by_id.insert(&tmp_token_id, &TokenMetadata::default());
+			base_metadata_id_by_token_id.insert(&tmp_token_id, &"a".repeat(64));
+		}
+		if let Some(metadata_creator_by_id) = &mut self.metadata_creator_by_id {
+			metadata_creator_by_id.insert(&tmp_token_id, &tmp_owner_id);
 		}
 
 		// 2. see how much space it took
 		self.extra_storage_in_bytes_per_nft_token = env::storage_usage() - initial_storage_usage;
 
-		if let Some(token_metadata_by_id) = &mut self.token_metadata_by_id {
+		if let (Some(token_metadata_by_id), Some(base_metadata_id_by_token_id)) =
+			(&mut self.token_metadata_by_id, &mut self.base_metadata_id_by_token_id)
+		{
 			token_metadata_by_id.remove(&tmp_token_id);
+			base_metadata_id_by_token_id.remove(&tmp_token_id);
+		}
+		if let Some(metadata_creator_by_id) = &mut self.metadata_creator_by_id {
+			metadata_creator_by_id.remove(&tmp_token_id);
 		}
 
 		self.nft_owner_by_id.remove(&tmp_token_id);
@@ -189,6 +319,120 @@ impl MultiToken {
 		LookupMap::new(self.get_balances_prefix())
 	}
 
+	/// Mint `amount` of a new or existing token id to `owner_id`. FT type tokens can be minted
+	/// again to grow their supply, while every NFT type token can only be minted once.
+	/// Metadata is only saved when the metadata extension is used.
+	pub fn internal_mint(
+		&mut self,
+		token_id: TokenId,
+		token_type: TokenType,
+		amount: Option<Balance>,
+		owner_id: AccountId,
+		token_metadata: Option<TokenMetadataAll>,
+	) {
+		if let Some(token_metadata) = &token_metadata {
+			token_metadata.assert_valid();
+		}
+
+		// Every token must have a token type and every NFT type cannot be re-minted
+		match self.token_type_index.get(&token_id) {
+			Some(TokenType::Ft) => {
+				require!(token_type == TokenType::Ft, "Type must be of FT time tokenId already exists")
+			}
+			Some(TokenType::Nft) => {
+				env::panic_str("Attempting to mint already minted NFT");
+			}
+			None => {
+				self.token_type_index.insert(&token_id, &token_type);
+				if let Some(all_token_ids) = &mut self.all_token_ids {
+					all_token_ids.insert(&token_id);
+				}
+				// Metadata extension: the account minting a new token may update its metadata
+				if let Some(metadata_creator_by_id) = &mut self.metadata_creator_by_id {
+					metadata_creator_by_id.insert(&token_id, &env::predecessor_account_id());
+				}
+			}
+		}
+
+		// Core behavior: every token must have an owner
+		match token_type {
+			TokenType::Ft => {
+				let amt = amount
+					.unwrap_or_else(|| env::panic_str("Amount must be specified for Ft type tokens"));
+				//create LookupMap for balances
+				match self.ft_owners_by_id.get(&token_id) {
+					Some(mut balances) => {
+						let current_bal = balances.get(&owner_id).unwrap_or(0);
+						// TODO not quite safe
+						if amt == 0 {
+							env::panic_str("error: amount should be greater than 0")
+						}
+						self.internal_checkpoint_balance(&token_id, &owner_id);
+						balances.insert(&owner_id, &(current_bal + amt));
+						let supply = self.ft_token_supply_by_id.get(&token_id).unwrap();
+						self.ft_token_supply_by_id.insert(&token_id, &(supply + amt));
+					}
+					None => {
+						// advance the prefix index before insertion
+						let mut balances = self.internal_new_ft_balances();
+						// insert amount into balances
+						balances.insert(&owner_id, &amt);
+						self.ft_owners_by_id.insert(&token_id, &balances);
+						self.ft_token_supply_by_id.insert(&token_id, &amt);
+					}
+				}
+				if amt > 0 {
+					self.internal_enumeration_add(&token_id, &owner_id);
+				}
+				MtMint { owner_id: &owner_id, token_ids: &[token_id.clone()], amounts: &[amt.into()], memo: None }
+					.emit();
+			}
+			TokenType::Nft => {
+				self.nft_owner_by_id.insert(&token_id, &owner_id);
+				self.internal_enumeration_add(&token_id, &owner_id);
+				MtMint { owner_id: &owner_id, token_ids: &[token_id.clone()], amounts: &[1.into()], memo: None }
+					.emit();
+			}
+		}
+
+		// Metadata extension: Save metadata, sharing the base metadata between tokens.
+		if let Some(token_metadata) = token_metadata.filter(|_| self.token_metadata_by_id.is_some()) {
+			self.internal_set_token_metadata(&token_id, &token_metadata);
+		}
+	}
+
+	/// Assembles the `Token` view of `token_id`. NFT type tokens always report their single
+	/// owner, FT type tokens only report `owner_id` when it is given, along with its approvals.
+	pub fn internal_get_token(
+		&self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		owner_id: Option<&AccountId>,
+	) -> Option<Token> {
+		let token_type = self.token_type_index.get(token_id)?;
+		let (owner_id, supply) = match token_type {
+			TokenType::Nft => (self.nft_owner_by_id.get(token_id), 1),
+			TokenType::Ft => (owner_id.cloned(), self.ft_token_supply_by_id.get(token_id).unwrap_or(0)),
+		};
+		let metadata = self.internal_get_token_metadata_all(token_id);
+		let approved_account_ids = self.approvals_by_id.as_ref().map(|approvals_by_id| {
+			owner_id
+				.as_ref()
+				.and_then(|owner_id| approvals_by_id.get(token_id).and_then(|approvals| approvals.get(owner_id).cloned()))
+				.map(|by_owner| {
+					by_owner.into_iter().map(|(account_id, approval)| (account_id, approval.approval_id)).collect()
+				})
+				.unwrap_or_default()
+		});
+		Some(Token {
+			token_id: token_id.clone(),
+			token_type,
+			owner_id,
+			supply: supply.into(),
+			metadata,
+			approved_account_ids,
+		})
+	}
+
 	pub fn internal_register_account(&mut self, token_id: TokenId, account_id: &AccountId) {
 		let token_type = self
 			.token_type_index
@@ -223,10 +467,12 @@ impl MultiToken {
 	) {
 		let balance = self.internal_unwrap_balance_of(token_id, account_id);
 		if let Some(new_balance) = balance.checked_add(amount) {
+			self.internal_checkpoint_balance(token_id, account_id);
 			self.ft_owners_by_id.get(token_id).unwrap().insert(&account_id, &new_balance);
 			let total_supply = self.ft_token_supply_by_id.get(token_id).unwrap();
 			let new_supply = total_supply.checked_add(amount).expect("Total supply overflow");
 			self.ft_token_supply_by_id.insert(token_id, &new_supply);
+			self.internal_enumeration_sync(token_id, account_id, new_balance);
 		} else {
 			env::panic_str("Balance overflow");
 		}
@@ -240,15 +486,122 @@ impl MultiToken {
 	) {
 		let balance = self.internal_unwrap_balance_of(token_id, account_id);
 		if let Some(new_balance) = balance.checked_sub(amount) {
+			self.internal_checkpoint_balance(token_id, account_id);
 			self.ft_owners_by_id.get(token_id).unwrap().insert(&account_id, &new_balance);
 			let total_supply = self.ft_token_supply_by_id.get(token_id).unwrap();
 			let new_supply = total_supply.checked_sub(amount).expect("Total supply overflow");
 			self.ft_token_supply_by_id.insert(token_id, &new_supply);
+			self.internal_enumeration_sync(token_id, account_id, new_balance);
 		} else {
 			env::panic_str("The account doesn't have enough balance");
 		}
 	}
 
+	/// Burn `amount` of the FT type `token_id` held by `account_id`, lowering its total supply.
+	/// Balances locked for other accounts can't be burned.
+	pub fn internal_burn(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+		amount: Balance,
+		memo: Option<String>,
+	) {
+		self.assert_transferable(token_id);
+		self.assert_unlocked_balance(token_id, account_id, amount);
+		self.internal_withdraw(token_id, account_id, amount);
+		self.internal_record_burn(token_id, amount);
+		MtBurn {
+			owner_id: account_id,
+			token_ids: &[token_id.clone()],
+			amounts: &[amount.into()],
+			authorized_id: None,
+			memo: memo.as_deref(),
+		}
+		.emit();
+	}
+
+	/// Burn `amount` of the FT type `token_id` on behalf of `sender_id`, who must either hold the
+	/// tokens or give the `approval` the owner granted them. Returns the owner the tokens were
+	/// burned from and whether an approval was spent, without emitting the burn event.
+	fn internal_burn_unlogged(
+		&mut self,
+		sender_id: &AccountId,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		amount: Balance,
+		approval: Option<(AccountId, u64)>,
+	) -> (AccountId, bool) {
+		let token_type = self.token_type_index.get(token_id).expect("Token not found");
+		require!(token_type == TokenType::Ft, "Only FT type tokens can be burned");
+		self.assert_transferable(token_id);
+		require!(amount > 0, "The amount should be a positive number");
+		let owner_id =
+			approval.as_ref().map(|(owner_id, _)| owner_id.clone()).unwrap_or_else(|| sender_id.clone());
+		let approved = if sender_id != &owner_id {
+			let (_, approval_id) = approval.unwrap();
+			self.internal_use_approval(token_id, &owner_id, sender_id, approval_id, amount);
+			true
+		} else {
+			false
+		};
+		self.assert_unlocked_balance(token_id, &owner_id, amount);
+		self.internal_withdraw(token_id, &owner_id, amount);
+		self.internal_record_burn(token_id, amount);
+		(owner_id, approved)
+	}
+
+	/// Adds `amount` to the burned counter of `token_id`. The supply itself must already be lowered.
+	pub fn internal_record_burn(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		amount: Balance,
+	) {
+		let burned = self.ft_burned_by_id.get(token_id).unwrap_or(0);
+		self.ft_burned_by_id.insert(token_id, &(burned + amount));
+	}
+
+	/// Burn the FT type `token_ids` on behalf of `sender_id`, logging a single burn event for the
+	/// whole batch. See [`internal_burn_unlogged`](Self::internal_burn_unlogged).
+	pub fn internal_burn_batch(
+		&mut self,
+		sender_id: &AccountId,
+		#[allow(clippy::ptr_arg)] token_ids: &Vec<TokenId>,
+		#[allow(clippy::ptr_arg)] amounts: &Vec<U128>,
+		approvals: Option<Vec<Option<(AccountId, u64)>>>,
+		memo: Option<String>,
+	) {
+		if token_ids.len() != amounts.len() {
+			env::panic_str("Number of token ids and amounts must be equal")
+		}
+		let mut approvals = approvals.unwrap_or_else(|| vec![None; token_ids.len()]);
+		if token_ids.len() != approvals.len() {
+			env::panic_str("Number of token ids and approvals must be equal")
+		}
+		let mut groups: Vec<(AccountId, bool, Vec<TokenId>, Vec<U128>)> = vec![];
+		for (idx, token_id) in token_ids.iter().enumerate() {
+			let (owner_id, approved) =
+				self.internal_burn_unlogged(sender_id, token_id, amounts[idx].into(), approvals[idx].take());
+			match groups.iter_mut().find(|(owner, appr, _, _)| owner == &owner_id && *appr == approved) {
+				Some((_, _, ids, amts)) => {
+					ids.push(token_id.clone());
+					amts.push(amounts[idx]);
+				}
+				None => groups.push((owner_id, approved, vec![token_id.clone()], vec![amounts[idx]])),
+			}
+		}
+		MtBurn::emit_many(
+			&groups
+				.iter()
+				.map(|(owner_id, approved, token_ids, amounts)| MtBurn {
+					owner_id,
+					token_ids,
+					amounts,
+					authorized_id: if *approved { Some(sender_id) } else { None },
+					memo: memo.as_deref(),
+				})
+				.collect::<Vec<_>>(),
+		);
+	}
+
 	/// Transfer token_id from `from` to `to`
 	///
 	///
@@ -263,6 +616,8 @@ impl MultiToken {
 		match self.token_type_index.get(token_id) {
 			Some(TokenType::Nft) => {
 				self.nft_owner_by_id.insert(token_id, to);
+				self.internal_enumeration_remove(token_id, from);
+				self.internal_enumeration_add(token_id, to);
 			}
 			Some(TokenType::Ft) => {
 				self.internal_withdraw(token_id, from, amount);
@@ -273,7 +628,7 @@ impl MultiToken {
 	}
 
 	fn verify_ft_transferable(
-		&self,
+		&mut self,
 		#[allow(clippy::ptr_arg)] token_id: &TokenId,
 		sender_id: &AccountId,
 		receiver_id: &AccountId,
@@ -287,37 +642,109 @@ impl MultiToken {
 		if balance < amount {
 			env::panic_str("Amount exceeds balance");
 		}
+		self.assert_unlocked_balance(token_id, sender_id, amount);
+	}
+
+	/// Returns the account `sender_id` moves `token_id` from: the owner of an NFT type token, or for
+	/// FT type tokens the owner named by the `approval` if given and `sender_id` otherwise.
+	pub fn internal_token_owner(
+		&self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		sender_id: &AccountId,
+		approval: Option<&(AccountId, u64)>,
+	) -> AccountId {
+		match self.token_type_index.get(token_id).expect("Token not found") {
+			TokenType::Nft => self.nft_owner_by_id.get(token_id).unwrap(),
+			TokenType::Ft => approval.map(|(owner_id, _)| owner_id.clone()).unwrap_or_else(|| sender_id.clone()),
+		}
+	}
+
+	/// Same as [`internal_token_owner`](Self::internal_token_owner) for each of `token_ids`,
+	/// `approvals` must be empty or have the same length as `token_ids`.
+	pub fn internal_token_owners(
+		&self,
+		token_ids: &[TokenId],
+		sender_id: &AccountId,
+		approvals: Option<&Vec<Option<(AccountId, u64)>>>,
+	) -> Vec<AccountId> {
+		token_ids
+			.iter()
+			.enumerate()
+			.map(|(idx, token_id)| {
+				let approval =
+					approvals.and_then(|approvals| approvals.get(idx)).and_then(Option::as_ref);
+				self.internal_token_owner(token_id, sender_id, approval)
+			})
+			.collect()
 	}
 
 	/// Transfer from current owner to receiver_id, checking that sender is allowed to transfer.
-	/// Clear approvals, if approval extension being used.
-	/// Return previous owner and approvals.
+	/// If the sender is not the owner, `approval` must name the owner and the approval_id
+	/// the sender was given, and the transferred amount is taken out of that approval.
+	/// Operators of the owner only need to name the owner, no approval is spent.
+	/// NFT type tokens have all of their approvals cleared as ownership changes.
+	/// Return previous owner and the spent approval, if any.
 	pub fn internal_transfer(
 		&mut self,
 		sender_id: &AccountId,
 		receiver_id: &AccountId,
 		#[allow(clippy::ptr_arg)] token_id: &TokenId,
 		amount: u128,
+		approval: Option<(AccountId, u64)>,
 		memo: Option<String>,
-	) {
+	) -> (AccountId, Option<(AccountId, u64, U128)>) {
+		let (owner_id, spent_approval) =
+			self.internal_transfer_unlogged(sender_id, receiver_id, token_id, amount, approval);
+		MtTransfer {
+			old_owner_id: &owner_id,
+			new_owner_id: receiver_id,
+			token_ids: &[token_id.clone()],
+			amounts: &[U128::from(amount)],
+			authorized_id: Some(sender_id).filter(|sender_id| *sender_id != &owner_id),
+			memo: memo.as_deref(),
+		}
+		.emit();
+		(owner_id, spent_approval)
+	}
+
+	/// Same as [`internal_transfer`](Self::internal_transfer), without emitting the transfer event.
+	/// Batch transfers use this to log a single event for the whole batch.
+	fn internal_transfer_unlogged(
+		&mut self,
+		sender_id: &AccountId,
+		receiver_id: &AccountId,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		amount: u128,
+		approval: Option<(AccountId, u64)>,
+	) -> (AccountId, Option<(AccountId, u64, U128)>) {
 		let token_type = self.token_type_index.get(token_id).expect("Token not found");
-		let mut owner_id = sender_id.clone();
+		self.assert_transferable(token_id);
+		let owner_id = self.internal_token_owner(token_id, sender_id, approval.as_ref());
+
+		let needs_approval =
+			sender_id != &owner_id && !self.internal_is_operator(&owner_id, sender_id);
+		let spent_approval = if needs_approval {
+			let approval_id = match approval {
+				Some((approval_owner_id, approval_id)) if approval_owner_id == owner_id => approval_id,
+				_ => env::panic_str("Unauthorized sender must be owner or approved"),
+			};
+			let spent = self.internal_use_approval(token_id, &owner_id, sender_id, approval_id, amount);
+			Some((sender_id.clone(), spent.approval_id, U128::from(spent.amount)))
+		} else {
+			None
+		};
+
 		match token_type {
 			TokenType::Nft => {
-				owner_id = self.nft_owner_by_id.get(token_id).unwrap();
 				assert_ne!(&owner_id, receiver_id, "Current and next owner must differ");
-				assert_eq!(&owner_id, sender_id, "Unauthorized sender must be owner");
+				self.internal_clear_approvals(token_id, &owner_id);
 			}
 			TokenType::Ft => {
-				self.verify_ft_transferable(token_id, sender_id, receiver_id, amount);
+				self.verify_ft_transferable(token_id, &owner_id, receiver_id, amount);
 			}
 		}
 		self.internal_transfer_unguarded(&token_id, amount, &owner_id, &receiver_id);
-		// TODO this might be problematic if 100 log limit and called from a looping construct
-		log!("Transfer {} from {} to {}", token_id, sender_id, receiver_id);
-		if let Some(memo) = memo {
-			log!("Memo: {}", memo);
-		}
+		(owner_id, spent_approval)
 	}
 
 	pub fn internal_transfer_batch(
@@ -326,21 +753,90 @@ impl MultiToken {
 		receiver_id: &AccountId,
 		#[allow(clippy::ptr_arg)] token_ids: &Vec<TokenId>,
 		#[allow(clippy::ptr_arg)] amounts: &Vec<U128>,
+		approvals: Option<Vec<Option<(AccountId, u64)>>>,
 		memo: Option<String>,
-	) {
+	) -> Vec<(AccountId, Option<(AccountId, u64, U128)>)> {
 		if token_ids.len() != amounts.len() {
 			env::panic_str("Number of token ids and amounts must be equal")
 		}
-		token_ids.iter().enumerate().for_each(|(idx, token_id)| {
-			self.internal_transfer(
-				sender_id,
-				receiver_id,
-				&token_id,
-				amounts[idx].into(),
-				memo.clone(),
-			)
+		let mut approvals = approvals.unwrap_or_else(|| vec![None; token_ids.len()]);
+		if token_ids.len() != approvals.len() {
+			env::panic_str("Number of token ids and approvals must be equal")
+		}
+		let results: Vec<(AccountId, Option<(AccountId, u64, U128)>)> = token_ids
+			.iter()
+			.enumerate()
+			.map(|(idx, token_id)| {
+				self.internal_transfer_unlogged(
+					sender_id,
+					receiver_id,
+					&token_id,
+					amounts[idx].into(),
+					approvals[idx].take(),
+				)
+			})
+			.collect();
+
+		// Log the whole batch as a single event to stay under the per receipt log limit
+		let transfers: Vec<(AccountId, AccountId, Option<AccountId>, TokenId, U128)> = results
+			.iter()
+			.enumerate()
+			.map(|(idx, (owner_id, _))| {
+				(
+					owner_id.clone(),
+					receiver_id.clone(),
+					Some(sender_id.clone()).filter(|sender_id| sender_id != owner_id),
+					token_ids[idx].clone(),
+					amounts[idx],
+				)
+			})
+			.collect();
+		emit_transfers(&transfers, memo.as_deref());
+		results
+	}
+}
+
+/// Log `(old_owner_id, new_owner_id, authorized_id, token_id, amount)` transfers as one event,
+/// grouping the token ids moved between the same accounts into a single entry
+fn emit_transfers(
+	transfers: &[(AccountId, AccountId, Option<AccountId>, TokenId, U128)],
+	memo: Option<&str>,
+) {
+	let mut groups: Vec<(&AccountId, &AccountId, Option<&AccountId>, Vec<TokenId>, Vec<U128>)> = vec![];
+	for (old_owner_id, new_owner_id, authorized_id, token_id, amount) in transfers.iter() {
+		let group = groups.iter_mut().find(|(old, new, authorized, _, _)| {
+			*old == old_owner_id && *new == new_owner_id && *authorized == authorized_id.as_ref()
 		});
+		match group {
+			Some((_, _, _, token_ids, amounts)) => {
+				token_ids.push(token_id.clone());
+				amounts.push(*amount);
+			}
+			None => groups.push((
+				old_owner_id,
+				new_owner_id,
+				authorized_id.as_ref(),
+				vec![token_id.clone()],
+				vec![*amount],
+			)),
+		}
+	}
+	if groups.is_empty() {
+		return;
 	}
+	MtTransfer::emit_many(
+		&groups
+			.iter()
+			.map(|(old_owner_id, new_owner_id, authorized_id, token_ids, amounts)| MtTransfer {
+				old_owner_id,
+				new_owner_id,
+				token_ids,
+				amounts,
+				authorized_id: *authorized_id,
+				memo,
+			})
+			.collect::<Vec<_>>(),
+	);
 }
 
 impl MultiTokenCore for MultiToken {
@@ -349,11 +845,21 @@ impl MultiTokenCore for MultiToken {
 		receiver_id: AccountId,
 		token_id: TokenId,
 		amount: U128,
+		approval: Option<(AccountId, u64)>,
 		memo: Option<String>,
 	) {
-		assert_one_yocto();
 		let sender_id = env::predecessor_account_id();
-		self.internal_transfer(&sender_id, &receiver_id, &token_id, amount.into(), memo);
+		if self.auto_register_receivers {
+			assert_at_least_one_yocto();
+			self.internal_storage_register_receiver(
+				&[token_id.clone()],
+				&receiver_id,
+				env::attached_deposit(),
+			);
+		} else {
+			assert_one_yocto();
+		}
+		self.internal_transfer(&sender_id, &receiver_id, &token_id, amount.into(), approval, memo);
 	}
 
 	fn mt_transfer_call(
@@ -361,15 +867,18 @@ impl MultiTokenCore for MultiToken {
 		receiver_id: AccountId,
 		token_id: TokenId,
 		amount: U128,
+		approval: Option<(AccountId, u64)>,
 		memo: Option<String>,
 		msg: String,
 	) -> PromiseOrValue<U128> {
 		assert_one_yocto();
 		let sender_id = env::predecessor_account_id();
-		self.internal_transfer(&sender_id, &receiver_id, &token_id, amount.into(), memo);
+		let (previous_owner_id, spent_approval) =
+			self.internal_transfer(&sender_id, &receiver_id, &token_id, amount.into(), approval, memo);
 		// Initiating receiver's call and the callback
 		ext_receiver::mt_on_transfer(
-			sender_id.clone(),
+			sender_id,
+			vec![previous_owner_id.clone()],
 			vec![token_id.clone()],
 			vec![amount],
 			msg,
@@ -378,10 +887,11 @@ impl MultiTokenCore for MultiToken {
 			env::prepaid_gas() - GAS_FOR_FT_TRANSFER_CALL,
 		)
 		.then(ext_self::mt_resolve_transfer(
-			sender_id,
+			vec![previous_owner_id],
 			receiver_id,
 			vec![token_id],
 			vec![amount],
+			Some(vec![spent_approval]),
 			env::current_account_id(),
 			NO_DEPOSIT,
 			GAS_FOR_RESOLVE_TRANSFER,
@@ -394,11 +904,17 @@ impl MultiTokenCore for MultiToken {
 		receiver_id: AccountId,
 		token_ids: Vec<TokenId>,
 		amounts: Vec<U128>,
+		approvals: Option<Vec<Option<(AccountId, u64)>>>,
 		memo: Option<String>,
 	) {
-		assert_one_yocto();
 		let sender_id = env::predecessor_account_id();
-		self.internal_transfer_batch(&sender_id, &receiver_id, &token_ids, &amounts, memo);
+		if self.auto_register_receivers {
+			assert_at_least_one_yocto();
+			self.internal_storage_register_receiver(&token_ids, &receiver_id, env::attached_deposit());
+		} else {
+			assert_one_yocto();
+		}
+		self.internal_transfer_batch(&sender_id, &receiver_id, &token_ids, &amounts, approvals, memo);
 	}
 
 	fn mt_batch_transfer_call(
@@ -406,12 +922,16 @@ impl MultiTokenCore for MultiToken {
 		receiver_id: AccountId,
 		token_ids: Vec<TokenId>,
 		amounts: Vec<U128>,
+		approvals: Option<Vec<Option<(AccountId, u64)>>>,
 		memo: Option<String>,
 		msg: String,
 	) -> PromiseOrValue<Vec<U128>> {
 		assert_one_yocto();
 		let sender_id = env::predecessor_account_id();
-		self.internal_transfer_batch(&sender_id, &receiver_id, &token_ids, &amounts, memo);
+		let (previous_owner_ids, spent_approvals): (Vec<AccountId>, Vec<Option<(AccountId, u64, U128)>>) =
+			self.internal_transfer_batch(&sender_id, &receiver_id, &token_ids, &amounts, approvals, memo)
+				.into_iter()
+				.unzip();
 		log!(
 			"Transferring data to:{} from sender: {}, p_gas: {}",
 			receiver_id,
@@ -420,7 +940,8 @@ impl MultiTokenCore for MultiToken {
 		);
 		// TODO make this efficient and calculate gas 
 		ext_receiver::mt_on_transfer(
-			sender_id.clone(),
+			sender_id,
+			previous_owner_ids.clone(),
 			token_ids.clone(),
 			amounts.clone(),
 			msg,
@@ -429,10 +950,11 @@ impl MultiTokenCore for MultiToken {
 			Gas(25_000_000_000_000),
 		)
 		.then(ext_self::mt_resolve_transfer(
-			sender_id,
+			previous_owner_ids,
 			receiver_id,
 			token_ids,
 			amounts,
+			Some(spent_approvals),
 			env::current_account_id(),
 			NO_DEPOSIT,
 			Gas(5_000_000_000_000),
@@ -461,6 +983,24 @@ impl MultiTokenCore for MultiToken {
 		token_ids.iter().map(|token_id| self.balance_of(owner_id.clone(), token_id.clone())).collect()
 	}
 
+	fn balance_of_accounts(&self, account_ids: Vec<AccountId>, token_id: TokenId) -> Vec<U128> {
+		account_ids
+			.into_iter()
+			.map(|account_id| self.balance_of(account_id, token_id.clone()))
+			.collect()
+	}
+
+	fn balance_of_accounts_batch(
+		&self,
+		account_ids: Vec<AccountId>,
+		token_ids: Vec<TokenId>,
+	) -> Vec<Vec<U128>> {
+		account_ids
+			.into_iter()
+			.map(|account_id| self.balance_of_batch(account_id, token_ids.clone()))
+			.collect()
+	}
+
 	fn total_supply(&self, token_id: TokenId) -> U128 {
 		self.ft_token_supply_by_id.get(&token_id).expect("supply: token id not found").into()
 	}
@@ -473,16 +1013,86 @@ impl MultiTokenCore for MultiToken {
 			})
 			.collect()
 	}
+
+	fn mt_burn(
+		&mut self,
+		token_id: TokenId,
+		amount: U128,
+		approval: Option<(AccountId, u64)>,
+		memo: Option<String>,
+	) {
+		assert_one_yocto();
+		let sender_id = env::predecessor_account_id();
+		let (owner_id, approved) = self.internal_burn_unlogged(&sender_id, &token_id, amount.into(), approval);
+		MtBurn {
+			owner_id: &owner_id,
+			token_ids: &[token_id],
+			amounts: &[amount],
+			authorized_id: if approved { Some(&sender_id) } else { None },
+			memo: memo.as_deref(),
+		}
+		.emit();
+	}
+
+	fn mt_batch_burn(
+		&mut self,
+		token_ids: Vec<TokenId>,
+		amounts: Vec<U128>,
+		approvals: Option<Vec<Option<(AccountId, u64)>>>,
+		memo: Option<String>,
+	) {
+		assert_one_yocto();
+		let sender_id = env::predecessor_account_id();
+		self.internal_burn_batch(&sender_id, &token_ids, &amounts, approvals, memo);
+	}
+
+	fn mt_supply(&self, token_id: TokenId) -> TokenSupply {
+		let (circulating, burned) =
+			match self.token_type_index.get(&token_id).expect("Token type does not exist") {
+				TokenType::Ft => (
+					self.ft_token_supply_by_id.get(&token_id).expect("supply: token id not found"),
+					self.ft_burned_by_id.get(&token_id).unwrap_or(0),
+				),
+				TokenType::Nft => (1, 0),
+			};
+		TokenSupply {
+			minted: (circulating + burned).into(),
+			burned: burned.into(),
+			circulating: circulating.into(),
+		}
+	}
+
+	fn mt_token(&self, token_id: TokenId) -> Option<Token> {
+		self.internal_get_token(&token_id, None)
+	}
+
+	fn mt_token_batch(&self, token_ids: Vec<TokenId>) -> Vec<Option<Token>> {
+		token_ids.iter().map(|token_id| self.internal_get_token(token_id, None)).collect()
+	}
 }
 
 impl MultiToken {
 	pub fn mt_internal_resolve_transfer(
 		&mut self,
-		sender_id: AccountId,
+		previous_owner_ids: Vec<AccountId>,
 		receiver_id: AccountId,
 		token_ids: Vec<TokenId>,
 		amounts: Vec<U128>,
+		approvals: Option<Vec<Option<(AccountId, u64, U128)>>>,
 	) -> Vec<U128> {
+		self.internal_resolve_transfer(previous_owner_ids, receiver_id, token_ids, amounts, approvals).0
+	}
+
+	/// Same as [`mt_internal_resolve_transfer`](Self::mt_internal_resolve_transfer), also returning
+	/// the tokens given back to their previous owners, as the previous owner, token and amount.
+	pub fn internal_resolve_transfer(
+		&mut self,
+		previous_owner_ids: Vec<AccountId>,
+		receiver_id: AccountId,
+		token_ids: Vec<TokenId>,
+		amounts: Vec<U128>,
+		approvals: Option<Vec<Option<(AccountId, u64, U128)>>>,
+	) -> (Vec<U128>, Vec<(AccountId, TokenId, U128)>) {
 		let returned_amounts: Vec<U128> = match env::promise_result(0) {
 			PromiseResult::NotReady => unreachable!(),
 			PromiseResult::Successful(value) => {
@@ -495,7 +1105,10 @@ impl MultiToken {
 			}
 			PromiseResult::Failed => amounts.clone(),
 		};
-		returned_amounts
+		let approvals = approvals.unwrap_or_else(|| vec![None; token_ids.len()]);
+		let mut refunds: Vec<(AccountId, AccountId, Option<AccountId>, TokenId, U128)> = vec![];
+		let mut burns: Vec<(TokenId, U128)> = vec![];
+		let resolved_amounts = returned_amounts
 			.iter()
 			.enumerate()
 			.map(|(idx, returned_amount)| {
@@ -503,6 +1116,14 @@ impl MultiToken {
 				if ret_amt == 0 {
 					return U128::from(0);
 				}
+				if !self.internal_is_transferable(&token_ids[idx]) {
+					log!(
+						"Transfers of {} are paused or frozen, the tokens are kept by the receiver",
+						token_ids[idx]
+					);
+					return amounts[idx];
+				}
+				let sender_id = &previous_owner_ids[idx];
 				match self.token_type_index.get(&token_ids[idx]).expect("Token type does not exist") {
 					TokenType::Ft => {
 						let unused_amount = std::cmp::min(amounts[idx].into(), returned_amount.clone().into());
@@ -510,13 +1131,35 @@ impl MultiToken {
 						let mut balances = self.ft_owners_by_id.get(&token_ids[idx]).expect(err_msg);
 						let receiver_balance =
 							balances.get(&receiver_id).expect("Token receiver no longer exists");
-						if receiver_balance > 0 {
-							let refund_amount: u128 = std::cmp::min(receiver_balance, unused_amount);
+						// Tokens the receiver has locked in the meantime stay with it
+						let locked = self.internal_locked_balance_of(&token_ids[idx], &receiver_id);
+						let refundable = receiver_balance.saturating_sub(locked);
+						if refundable > 0 {
+							let refund_amount: u128 = std::cmp::min(refundable, unused_amount);
+							self.internal_checkpoint_balance_on_resolve(&token_ids[idx], &receiver_id, sender_id);
 							balances.insert(&receiver_id, &(receiver_balance - refund_amount));
-							return match balances.get(&sender_id) {
+							self.internal_enumeration_sync(&token_ids[idx], &receiver_id, receiver_balance - refund_amount);
+							return match balances.get(sender_id) {
 								Some(sender_balance) => {
-									balances.insert(&sender_id, &(sender_balance + refund_amount));
-									log!("Refund {} from {} to {}", refund_amount, receiver_id, sender_id);
+									self.internal_checkpoint_balance_on_resolve(&token_ids[idx], sender_id, sender_id);
+									balances.insert(sender_id, &(sender_balance + refund_amount));
+									self.internal_enumeration_add(&token_ids[idx], sender_id);
+									refunds.push((
+										receiver_id.clone(),
+										sender_id.clone(),
+										None,
+										token_ids[idx].clone(),
+										U128::from(refund_amount),
+									));
+									if let Some((approved_id, approval_id, _)) = &approvals[idx] {
+										self.internal_restore_approval(
+											&token_ids[idx],
+											sender_id,
+											approved_id,
+											*approval_id,
+											refund_amount,
+										);
+									}
 									let amount: u128 = amounts[idx].into();
 									U128::from(amount - refund_amount)
 								}
@@ -524,6 +1167,8 @@ impl MultiToken {
 									let supply =
 										self.ft_token_supply_by_id.get(&token_ids[idx]).expect("Token has no supply");
 									self.ft_token_supply_by_id.insert(&token_ids[idx], &(supply - refund_amount));
+									self.internal_record_burn(&token_ids[idx], refund_amount);
+									burns.push((token_ids[idx].clone(), U128::from(refund_amount)));
 									log!("The account of the sender was deleted");
 									U128::from(0)
 								}
@@ -537,8 +1182,24 @@ impl MultiToken {
 							return if current_owner != receiver_id {
 								U128::from(0)
 							} else {
-								log!("Return token {} from @{} to @{}", token_ids[idx], &receiver_id, &sender_id);
-								self.internal_transfer_unguarded(&token_ids[idx], 1, &receiver_id, &sender_id);
+								refunds.push((
+									receiver_id.clone(),
+									sender_id.clone(),
+									None,
+									token_ids[idx].clone(),
+									U128::from(1),
+								));
+								self.internal_transfer_unguarded(&token_ids[idx], 1, &receiver_id, sender_id);
+								self.internal_clear_approvals(&token_ids[idx], &receiver_id);
+								if let Some((approved_id, approval_id, _)) = &approvals[idx] {
+									self.internal_restore_approval(
+										&token_ids[idx],
+										sender_id,
+										approved_id,
+										*approval_id,
+										1,
+									);
+								}
 								U128::from(1)
 							}
 						}
@@ -546,18 +1207,38 @@ impl MultiToken {
 					}
 				}
 			})
-			.collect()
+			.collect();
+
+		emit_transfers(&refunds, None);
+		// Tokens that could not be given back to a deleted sender are burned
+		if !burns.is_empty() {
+			let (token_ids, amounts): (Vec<TokenId>, Vec<U128>) = burns.into_iter().unzip();
+			MtBurn {
+				owner_id: &receiver_id,
+				token_ids: &token_ids,
+				amounts: &amounts,
+				authorized_id: None,
+				memo: Some("refund"),
+			}
+			.emit();
+		}
+		let refunds = refunds
+			.into_iter()
+			.map(|(_, previous_owner_id, _, token_id, amount)| (previous_owner_id, token_id, amount))
+			.collect();
+		(resolved_amounts, refunds)
 	}
 }
 
 impl MultiTokenResolver for MultiToken {
 	fn mt_resolve_transfer(
 		&mut self,
-		sender_id: AccountId,
+		previous_owner_ids: Vec<AccountId>,
 		receiver_id: AccountId,
 		token_ids: Vec<TokenId>,
 		amounts: Vec<U128>,
+		approvals: Option<Vec<Option<(AccountId, u64, U128)>>>,
 	) -> Vec<U128> {
-		self.mt_internal_resolve_transfer(sender_id, receiver_id, token_ids, amounts)
+		self.mt_internal_resolve_transfer(previous_owner_ids, receiver_id, token_ids, amounts, approvals)
 	}
 }
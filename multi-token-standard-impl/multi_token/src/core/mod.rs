@@ -9,7 +9,7 @@ pub use self::storage_impl::*;
 pub use self::receiver::*;
 pub use self::resolver::*;
 
-use crate::token::TokenId;
+use crate::token::{Token, TokenId, TokenSupply};
 use near_sdk::json_types::U128;
 use near_sdk::{AccountId, PromiseOrValue};
 
@@ -20,15 +20,20 @@ pub trait MultiTokenCore {
     /// Requirements
     /// * Caller of the method must attach a deposit of 1 yoctoⓃ for security purposes
     /// * Contract MUST panic if called by someone other than token owner or,
+    ///   if using Approval Management, one of the approved accounts
     /// * If using Approval Management, contract MUST nullify approved accounts on
     ///   successful transfer.
-    /// * TODO: needed? Both accounts must be registered with the contract for transfer to
-    ///   succeed. See see https://nomicon.io/Standards/StorageManagement.html
+    /// * Both accounts must be registered with the contract for transfer to succeed, see
+    ///   https://nomicon.io/Standards/StorageManagement.html. If the implementation opts in to
+    ///   registering receivers, the deposit attached above 1 yoctoⓃ or the storage balance of
+    ///   the receiver pays for its registration instead, with the excess deposit refunded.
     ///
     /// Arguments:
     /// * `receiver_id`: the valid NEAR account receiving the token
     /// * `token_id`: the token or tokens to transfer
     /// * `amount`: the token amount of tokens to transfer for token_id
+    /// * `approval` (optional): the owner of the tokens and the approval ID the caller was
+    ///    given, for use with Approval Management
     /// * `memo` (optional): for use cases that may benefit from indexing or
     ///    providing information for a transfer
     fn mt_transfer(
@@ -36,6 +41,7 @@ pub trait MultiTokenCore {
         receiver_id: AccountId,
         token_id: TokenId,
         amount: U128,
+        approval: Option<(AccountId, u64)>,
         memo: Option<String>,
     );
 
@@ -61,6 +67,8 @@ pub trait MultiTokenCore {
     /// * `receiver_id`: the valid NEAR account receiving the token.
     /// * `token_id`: the token to send.
     /// * `amount`: amount of tokens to transfer for token_id
+    /// * `approval` (optional): the owner of the tokens and the approval ID the caller was
+    ///    given, for use with Approval Management
     /// * `memo` (optional): for use cases that may benefit from indexing or
     ///    providing information for a transfer.
     /// * `msg`: specifies information needed by the receiving contract in
@@ -71,6 +79,7 @@ pub trait MultiTokenCore {
         receiver_id: AccountId,
         token_id: TokenId,
         amount: U128,
+        approval: Option<(AccountId, u64)>,
         memo: Option<String>,
         msg: String,
     ) -> PromiseOrValue<U128>;
@@ -87,8 +96,10 @@ pub trait MultiTokenCore {
     ///   see https://nomicon.io/Standards/NonFungibleToken/ApprovalManagement.html
     /// * If using Approval Management, contract MUST nullify approved accounts on
     ///   successful transfer.
-    /// * TODO: needed? Both accounts must be registered with the contract for transfer to
-    ///   succeed. See see https://nomicon.io/Standards/StorageManagement.html
+    /// * Both accounts must be registered with the contract for transfer to succeed, see
+    ///   https://nomicon.io/Standards/StorageManagement.html. If the implementation opts in to
+    ///   registering receivers, the deposit attached above 1 yoctoⓃ or the storage balance of
+    ///   the receiver pays for its registration instead, with the excess deposit refunded.
     /// * The token_ids vec and amounts vec must be of equal length and equate to a 1-1 mapping
     ///   between amount and id. In the event that they do not line up the call should fail
     ///
@@ -96,9 +107,10 @@ pub trait MultiTokenCore {
     /// * `receiver_id`: the valid NEAR account receiving the token
     /// * `token_ids`: the tokens to transfer
     /// * `amounts`: the amount of tokens to transfer for corresponding token_id
-    /// * `approval_ids`: expected approval ID. A number smaller than
-    ///    2^53, and therefore representable as JSON. See Approval Management
-    ///    standard for full explanation. Must have same length as token_ids
+    /// * `approvals` (optional): the owner and expected approval ID for each token. The
+    ///    approval ID is a number smaller than 2^53, and therefore representable as JSON.
+    ///    See Approval Management standard for full explanation. Must have same length
+    ///    as token_ids
     /// * `memo` (optional): for use cases that may benefit from indexing or
     ///    providing information for a transfer
 
@@ -107,6 +119,7 @@ pub trait MultiTokenCore {
         receiver_id: AccountId,
         token_id: Vec<TokenId>,
         amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
         memo: Option<String>,
     );
     /// Batch transfer token/s and call a method on a receiver contract. A successful
@@ -135,9 +148,10 @@ pub trait MultiTokenCore {
     /// * `receiver_id`: the valid NEAR account receiving the token.
     /// * `token_ids`: the tokens to transfer
     /// * `amounts`: the amount of tokens to transfer for corresponding token_id
-    /// * `approval_ids`: expected approval IDs. A number smaller than
-    ///    2^53, and therefore representable as JSON. See Approval Management
-    ///    standard for full explanation. Must have same length as token_ids
+    /// * `approvals` (optional): the owner and expected approval ID for each token. The
+    ///    approval ID is a number smaller than 2^53, and therefore representable as JSON.
+    ///    See Approval Management standard for full explanation. Must have same length
+    ///    as token_ids
     /// * `memo` (optional): for use cases that may benefit from indexing or
     ///    providing information for a transfer.
     /// * `msg`: specifies information needed by the receiving contract in
@@ -149,10 +163,44 @@ pub trait MultiTokenCore {
         receiver_id: AccountId,
         token_ids: Vec<TokenId>,
         amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
         memo: Option<String>,
         msg: String,
     ) -> PromiseOrValue<Vec<U128>>;
 
+    /// Burn an amount of a FT type token, lowering its total supply.
+    ///
+    /// Requirements
+    /// * Caller of the method must attach a deposit of 1 yoctoⓃ for security purposes
+    /// * Contract MUST panic if called by someone other than token owner or,
+    ///   if using Approval Management, an account approved for at least `amount`
+    /// * NFT type tokens cannot be burned
+    ///
+    /// Arguments:
+    /// * `token_id`: the token to burn
+    /// * `amount`: the amount of tokens to burn
+    /// * `approval` (optional): the owner of the tokens and the approval ID the caller was
+    ///    given, for use with Approval Management
+    /// * `memo` (optional): for use cases that may benefit from indexing or
+    ///    providing information for a burn
+    fn mt_burn(
+        &mut self,
+        token_id: TokenId,
+        amount: U128,
+        approval: Option<(AccountId, u64)>,
+        memo: Option<String>,
+    );
+
+    /// Batch version of `mt_burn`. The token_ids, amounts and approvals vecs must be of equal
+    /// length and a single burn event is logged for the whole batch.
+    fn mt_batch_burn(
+        &mut self,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
+        memo: Option<String>,
+    );
+
     /// Get the balance of an an account given token_id. For fungible token returns back amount, for
     /// non fungible token it returns back constant 1.
     fn balance_of(&self, owner_id: AccountId, token_id: TokenId) -> U128;
@@ -162,9 +210,32 @@ pub trait MultiTokenCore {
     /// in a 1-1 mapping
     fn balance_of_batch(&self, owner_id: AccountId, token_ids: Vec<TokenId>) -> Vec<U128>;
 
+    /// Get the balances of many accounts for a single token_id, returns vector of balances
+    /// corresponding to account_ids in a 1-1 mapping
+    fn balance_of_accounts(&self, account_ids: Vec<AccountId>, token_id: TokenId) -> Vec<U128>;
+
+    /// Get the balances of many accounts for many token_ids. Returns one row per account in
+    /// account_ids, each holding the balances corresponding to token_ids
+    fn balance_of_accounts_batch(
+        &self,
+        account_ids: Vec<AccountId>,
+        token_ids: Vec<TokenId>,
+    ) -> Vec<Vec<U128>>;
+
     /// Returns the total supply of the token in a decimal string representation given token_id.
     fn total_supply(&self, token_id: TokenId) -> U128;
 
     // Returns the total supplies of the tokens given by token_ids in a decimal string representation.
     fn total_supply_batch(&self, token_ids: Vec<TokenId>) -> Vec<U128>;
+
+    /// Returns how much of the token was minted and burned, along with the circulating supply
+    /// which is the same as `total_supply`.
+    fn mt_supply(&self, token_id: TokenId) -> TokenSupply;
+
+    /// Returns the token with the given token_id, including its type, supply, metadata and, for
+    /// NFT type tokens, its owner and approved accounts. Returns None if the token does not exist.
+    fn mt_token(&self, token_id: TokenId) -> Option<Token>;
+
+    /// Returns the tokens given by token_ids in a 1-1 mapping, with None for the ids that do not exist.
+    fn mt_token_batch(&self, token_ids: Vec<TokenId>) -> Vec<Option<Token>>;
 }
@@ -22,19 +22,21 @@ pub trait MultiTokenResolver {
     ///   `sender_id`
     ///
     /// Arguments:
-    /// * `previous_owner_id`: the owner prior to the call to `mt_transfer_call`
+    /// * `previous_owner_ids`: the owner of each token prior to the call to `mt_transfer_call`
     /// * `receiver_id`: the `receiver_id` argument given to `mt_transfer_call`
     /// * `token_ids`: the `token_ids` argument given to `mt_transfer_call`
+    /// * `amounts`: the `amounts` argument given to `mt_transfer_call`
     /// * `approvals`: if using Approval Management, contract MUST provide
-    ///   set of original approved accounts in this argument, and restore these
-    ///   approved accounts in case of revert. In this case it may be multiple sets of approvals
+    ///   the approved account, approval ID and spent amount for each token that was
+    ///   moved through an approval, and restore these approvals in case of revert.
     ///
-    /// Returns true if tokens were successfully transferred to `receiver_id`.
+    /// Returns the amounts of each token that were kept by `receiver_id`.
     fn mt_resolve_transfer(
         &mut self,
-        sender_id: AccountId,
+        previous_owner_ids: Vec<AccountId>,
         receiver_id: AccountId,
         token_ids: Vec<TokenId>,
         amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64, U128)>>>,
     ) -> Vec<U128>;
 }
@@ -1,16 +1,40 @@
+use crate::events::MtBurn;
 use crate::storage_management::{StorageBalance, StorageBalanceBounds, StorageManagement};
 use crate::MultiToken;
 use crate::{TokenId, TokenType};
 use near_sdk::json_types::U128;
-use near_sdk::{assert_one_yocto, env, AccountId, Balance, Promise};
+use near_sdk::{assert_one_yocto, env, AccountId, Balance, Promise, StorageUsage};
 
 impl MultiToken {
+  /// Unregisters the predecessor account from `token_id`. Any balance dropped with `force` is
+  /// burned and logged as such.
   pub fn internal_storage_unregister(
     &mut self,
     token_id: TokenId,
     force: Option<bool>,
   ) -> Option<(AccountId, Balance)> {
     assert_one_yocto();
+    let unregistered = self.internal_storage_unregister_unlogged(&token_id, force);
+    if let Some((account_id, balance)) = unregistered.as_ref().filter(|(_, balance)| *balance > 0) {
+      MtBurn {
+        owner_id: account_id,
+        token_ids: &[token_id],
+        amounts: &[U128::from(*balance)],
+        authorized_id: None,
+        memo: Some("unregister"),
+      }
+      .emit();
+    }
+    unregistered
+  }
+
+  /// Same as [`internal_storage_unregister`](Self::internal_storage_unregister), without
+  /// emitting the burn event.
+  fn internal_storage_unregister_unlogged(
+    &mut self,
+    #[allow(clippy::ptr_arg)] token_id: &TokenId,
+    force: Option<bool>,
+  ) -> Option<(AccountId, Balance)> {
     let account_id = env::predecessor_account_id();
     let force = force.unwrap_or(false);
     let token_type = self
@@ -20,16 +44,21 @@ impl MultiToken {
     if token_type == TokenType::Nft {
       return None;
     }
-    let balance =
-      self.ft_owners_by_id.get(&token_id).unwrap().get(&account_id).unwrap_or_else(|| {
-        env::panic_str(format!("Could not find token_id: {} owner for {}", token_id, account_id).as_str())
-      });
+    let balance = self.ft_owners_by_id.get(&token_id).unwrap().get(&account_id)?;
+    if self.internal_locked_balance_of(token_id, &account_id) > 0 {
+      env::panic_str("Can't unregister the account while part of its balance is locked");
+    }
     if balance == 0 || force {
+      self.internal_remove_expired_locks(token_id, &account_id);
+      self.internal_checkpoint_balance(token_id, &account_id);
       self.ft_owners_by_id.get(&token_id).unwrap().remove(&account_id);
+      self.internal_enumeration_remove(&token_id, &account_id);
       let updated_supply = self.ft_token_supply_by_id.get(&token_id).unwrap() - balance;
       self.ft_token_supply_by_id.insert(&token_id, &updated_supply);
-      Promise::new(account_id.clone())
-        .transfer(self.internal_storage_balance_bounds(&token_id, None).min.0 + 1);
+      if balance > 0 {
+        self.internal_record_burn(token_id, balance);
+      }
+      self.internal_storage_release(token_id, &account_id);
       Some((account_id, balance))
     } else {
       env::panic_str("Can't unregister the account with the positive balance without force")
@@ -37,18 +66,161 @@ impl MultiToken {
   }
 
   /// Internal method that returns the Account ID and the balance in case the account was
-  /// unregistered.
-  /// TODO double check logic here and make more efficient
+  /// unregistered. The balances dropped with `force` are logged as a single burn event.
   pub fn internal_storage_unregister_batch(
     &mut self,
     token_ids: Vec<TokenId>,
     force: Option<bool>,
   ) -> Vec<Option<(AccountId, Balance)>> {
     assert_one_yocto();
+    let unregistered: Vec<Option<(AccountId, Balance)>> = token_ids
+      .iter()
+      .map(|token_id| self.internal_storage_unregister_unlogged(token_id, force))
+      .collect();
+    let (burned_ids, burned_amounts): (Vec<TokenId>, Vec<U128>) = token_ids
+      .iter()
+      .zip(unregistered.iter())
+      .filter_map(|(token_id, unregistered)| match unregistered {
+        Some((_, balance)) if *balance > 0 => Some((token_id.clone(), U128::from(*balance))),
+        _ => None,
+      })
+      .unzip();
+    if !burned_ids.is_empty() {
+      MtBurn {
+        owner_id: &env::predecessor_account_id(),
+        token_ids: &burned_ids,
+        amounts: &burned_amounts,
+        authorized_id: None,
+        memo: Some("unregister"),
+      }
+      .emit();
+    }
+    unregistered
+  }
+
+  /// The balances of `account_id` which unregistering from `token_ids` with `force` burns
+  pub fn internal_storage_forced_burns(
+    &self,
+    token_ids: &[TokenId],
+    account_id: &AccountId,
+  ) -> (Vec<TokenId>, Vec<U128>) {
     token_ids
       .iter()
-      .map(|token_id| self.internal_storage_unregister(token_id.clone(), force))
-      .collect()
+      .filter_map(|token_id| {
+        let balance = self.ft_owners_by_id.get(token_id)?.get(account_id)?;
+        Some((token_id.clone(), U128::from(balance))).filter(|_| balance > 0)
+      })
+      .unzip()
+  }
+
+  /// Registers `account_id` for the FT type `token_id`, paying for it out of the available
+  /// storage balance of the account. Does nothing if the account is already registered.
+  pub fn internal_storage_register(
+    &mut self,
+    #[allow(clippy::ptr_arg)] token_id: &TokenId,
+    account_id: &AccountId,
+  ) {
+    let cost = self.internal_storage_balance_bounds(token_id, Some(account_id.clone())).min.0;
+    if cost == 0 {
+      return;
+    }
+    let mut balance = self
+      .storage_balances
+      .get(account_id)
+      .unwrap_or(StorageBalance { total: 0.into(), available: 0.into() });
+    if balance.available.0 < cost {
+      env::panic_str(
+        format!(
+          "The storage balance of {} is too low to register for {}, {} yoctoNEAR is required",
+          account_id, token_id, cost
+        )
+        .as_str(),
+      );
+    }
+    balance.available = (balance.available.0 - cost).into();
+    self.storage_balances.insert(account_id, &balance);
+    self.storage_paid_registrations.insert(&(token_id.clone(), account_id.clone()), &cost);
+    self.internal_register_account(token_id.clone(), account_id);
+  }
+
+  /// Registers `receiver_id` for every FT type token of `token_ids` it is not yet registered for,
+  /// paying out of its own storage balance when it has enough available. Otherwise the shortfall
+  /// is taken from `deposit` and added to the storage balance of `receiver_id`, so it gets it back
+  /// once it unregisters. The part of `deposit` left over is refunded.
+  pub fn internal_storage_register_receiver(
+    &mut self,
+    token_ids: &[TokenId],
+    receiver_id: &AccountId,
+    deposit: Balance,
+  ) {
+    let mut deposit_left = deposit;
+    for token_id in token_ids.iter() {
+      let cost = self.internal_storage_balance_bounds(token_id, Some(receiver_id.clone())).min.0;
+      if cost == 0 {
+        continue;
+      }
+      let initial_storage_usage = env::storage_usage();
+      let mut balance = self
+        .storage_balances
+        .get(receiver_id)
+        .unwrap_or(StorageBalance { total: 0.into(), available: 0.into() });
+      self.storage_balances.insert(receiver_id, &balance);
+      // A receiver without a storage balance also pays for storing it
+      let balance_cost =
+        Balance::from(env::storage_usage() - initial_storage_usage) * env::storage_byte_cost();
+      let shortfall = (cost + balance_cost).saturating_sub(balance.available.0);
+      if deposit_left < shortfall {
+        env::panic_str(
+          format!(
+            "Must attach {} yoctoNEAR to register {} for {}",
+            shortfall, receiver_id, token_id
+          )
+          .as_str(),
+        );
+      }
+      deposit_left -= shortfall;
+      balance.total = (balance.total.0 + shortfall).into();
+      balance.available = (balance.available.0 + shortfall - balance_cost).into();
+      self.storage_balances.insert(receiver_id, &balance);
+      self.internal_storage_register(token_id, receiver_id);
+    }
+    if deposit_left > 1 {
+      Promise::new(env::predecessor_account_id()).transfer(deposit_left);
+    }
+  }
+
+  /// Pays for `storage_used` out of the available storage balance of the first of `payers` which
+  /// has enough of it. Returns whether one could pay.
+  pub fn internal_storage_charge(
+    &mut self,
+    payers: &[&AccountId],
+    storage_used: StorageUsage,
+  ) -> bool {
+    let cost = Balance::from(storage_used) * env::storage_byte_cost();
+    for account_id in payers {
+      if let Some(mut balance) = self.storage_balances.get(account_id) {
+        if balance.available.0 >= cost {
+          balance.available = (balance.available.0 - cost).into();
+          self.storage_balances.insert(account_id, &balance);
+          return true;
+        }
+      }
+    }
+    false
+  }
+
+  /// Gives the storage a registration drew from the storage balance of `account_id` back to it.
+  /// Registrations paid for any other way, such as at mint, release nothing.
+  fn internal_storage_release(
+    &mut self,
+    #[allow(clippy::ptr_arg)] token_id: &TokenId,
+    account_id: &AccountId,
+  ) {
+    let paid = self.storage_paid_registrations.remove(&(token_id.clone(), account_id.clone()));
+    if let (Some(paid), Some(mut balance)) = (paid, self.storage_balances.get(account_id)) {
+      balance.available = (balance.available.0 + paid).into();
+      self.storage_balances.insert(account_id, &balance);
+    }
   }
 
   fn internal_storage_balance_bounds(
@@ -79,64 +251,25 @@ impl MultiToken {
     }
   }
 
+  /// The storage balance is pooled, so there is no maximum to how much an account can deposit
   pub fn internal_storage_balance_bounds_batch(
     &self,
     token_ids: &[TokenId],
     account_id: Option<AccountId>,
   ) -> StorageBalanceBounds {
-    let mut min_storage: u128 = 0;
-    let mut max_storage: u128 = 0;
-
-    token_ids.iter().for_each(|token_id| {
-      let bound = self.internal_storage_balance_bounds(token_id, account_id.clone());
-      min_storage += u128::from(bound.min);
-      max_storage += u128::from(bound.max.unwrap_or_else(|| 0.into()));
-    });
-    StorageBalanceBounds { min: min_storage.into(), max: Some(max_storage.into()) }
-  }
-
-  pub fn internal_storage_balance_of(
-    &self,
-    token_id: TokenId,
-    account_id: &AccountId,
-  ) -> Option<StorageBalance> {
-    let token_type = self
-      .token_type_index
-      .get(&token_id)
-      .unwrap_or_else(|| env::panic_str(format!("Could not find token_id {}", token_id).as_str()));
-    if token_type == TokenType::Nft {
-      return None;
-    }
-    let min_storage = self.internal_storage_balance_bounds(&token_id, None).min;
-    if self.ft_owners_by_id.get(&token_id).unwrap().contains_key(account_id) {
-      Some(StorageBalance { total: min_storage, available: 0.into() })
-    } else {
-      None
-    }
+    let min_storage: u128 = token_ids
+      .iter()
+      .map(|token_id| self.internal_storage_balance_bounds(token_id, account_id.clone()).min.0)
+      .sum();
+    StorageBalanceBounds { min: min_storage.into(), max: None }
   }
 
-  pub fn internal_storage_balance_of_batch(
-    &self,
-    token_ids: &[TokenId],
-    account_id: &AccountId,
-  ) -> Option<StorageBalance> {
-    let mut total: u128 = 0;
-    token_ids.iter().for_each(|token_id| {
-      if let Some(balance) = self.internal_storage_balance_of(token_id.clone(), account_id) {
-        total += u128::from(balance.total);
-      };
-    });
-    if total == 0 {
-      return None;
-    }
-    Some(StorageBalance { total: total.into(), available: 0.into() })
+  pub fn internal_storage_balance_of(&self, account_id: &AccountId) -> Option<StorageBalance> {
+    self.storage_balances.get(account_id)
   }
 }
 
 impl StorageManagement for MultiToken {
-  // `registration_only` doesn't affect the implementation for vanilla fungible token.
-  // TODO make more efficient
-  #[allow(unused_variables)]
   fn storage_deposit(
     &mut self,
     token_ids: Vec<TokenId>,
@@ -145,48 +278,67 @@ impl StorageManagement for MultiToken {
   ) -> StorageBalance {
     let amount: Balance = env::attached_deposit();
     let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
-    let min_balance =
-      self.internal_storage_balance_bounds_batch(&token_ids, Some(account_id.clone())).min.0;
-    if amount < min_balance {
-      env::panic_str("The attached deposit is less than the minimum storage balance");
+    let initial_storage_usage = env::storage_usage();
+    let mut balance = self
+      .storage_balances
+      .get(&account_id)
+      .unwrap_or(StorageBalance { total: 0.into(), available: 0.into() });
+    balance.total = (balance.total.0 + amount).into();
+    balance.available = (balance.available.0 + amount).into();
+    self.storage_balances.insert(&account_id, &balance);
+
+    // The first deposit of an account also pays for storing its balance
+    let balance_cost =
+      Balance::from(env::storage_usage() - initial_storage_usage) * env::storage_byte_cost();
+    if balance_cost > 0 {
+      if balance.available.0 < balance_cost {
+        env::panic_str("The attached deposit is less than the minimum storage balance");
+      }
+      balance.available = (balance.available.0 - balance_cost).into();
+      self.storage_balances.insert(&account_id, &balance);
     }
+
     token_ids.iter().for_each(|token_id| {
-      self.internal_register_account(token_id.clone(), &account_id);
+      self.internal_storage_register(token_id, &account_id);
     });
-    let refund = amount - min_balance;
-    if refund > 0 {
-      Promise::new(env::predecessor_account_id()).transfer(refund);
+
+    let mut balance = self.storage_balances.get(&account_id).unwrap();
+    if registration_only.unwrap_or(false) {
+      let refund = std::cmp::min(amount, balance.available.0);
+      if refund > 0 {
+        balance.total = (balance.total.0 - refund).into();
+        balance.available = (balance.available.0 - refund).into();
+        self.storage_balances.insert(&account_id, &balance);
+        Promise::new(env::predecessor_account_id()).transfer(refund);
+      }
     }
-    self.internal_storage_balance_of_batch(&token_ids, &account_id).unwrap()
+    balance
   }
 
-  /// While storage_withdraw normally allows the caller to retrieve `available` balance, the basic
-  /// Fungible Token implementation sets storage_balance_bounds.min == storage_balance_bounds.max,
-  /// which means available balance will always be 0. So this implementation:
-  /// * panics if `amount > 0`
-  /// * never transfers Ⓝ to caller
-  /// * returns a `storage_balance` struct if `amount` is 0
-  fn storage_withdraw(&mut self, token_ids: Vec<TokenId>, amount: Option<U128>) -> StorageBalance {
+  fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
     assert_one_yocto();
     let predecessor_account_id = env::predecessor_account_id();
-    if let Some(storage_balance) =
-      self.internal_storage_balance_of_batch(&token_ids, &predecessor_account_id)
-    {
-      match amount {
-        Some(amount) if amount.0 > 0 => {
-          env::panic_str("The amount is greater than the available storage balance");
-        }
-        _ => storage_balance,
-      }
-    } else {
-      env::panic_str(format!("The account {} is not registered", &predecessor_account_id).as_ref());
+    let mut balance = self.storage_balances.get(&predecessor_account_id).unwrap_or_else(|| {
+      env::panic_str(format!("The account {} is not registered", &predecessor_account_id).as_ref())
+    });
+    let amount = amount.map(|amount| amount.0).unwrap_or(balance.available.0);
+    if amount > balance.available.0 {
+      env::panic_str("The amount is greater than the available storage balance");
     }
+    if amount > 0 {
+      balance.total = (balance.total.0 - amount).into();
+      balance.available = (balance.available.0 - amount).into();
+      self.storage_balances.insert(&predecessor_account_id, &balance);
+      Promise::new(predecessor_account_id).transfer(amount);
+    }
+    balance
   }
 
   fn storage_unregister(&mut self, token_ids: Vec<TokenId>, force: Option<bool>) -> Vec<bool> {
-    token_ids
+    self
+      .internal_storage_unregister_batch(token_ids, force)
       .iter()
-      .map(|token_id| self.internal_storage_unregister(token_id.clone(), force).is_some())
+      .map(|unregistered| unregistered.is_some())
       .collect()
   }
   // Storage requirements for TokenIds associated with NFT times are waved
@@ -197,22 +349,10 @@ impl StorageManagement for MultiToken {
     token_ids: Vec<TokenId>,
     account_id: Option<AccountId>,
   ) -> StorageBalanceBounds {
-    let mut min_storage: u128 = 0;
-    let mut max_storage: u128 = 0;
-
-    token_ids.iter().for_each(|token_id| {
-      let bound = self.internal_storage_balance_bounds(token_id, account_id.clone());
-      min_storage += u128::from(bound.min);
-      max_storage += u128::from(bound.max.unwrap_or_else(|| 0.into()));
-    });
-    StorageBalanceBounds { min: min_storage.into(), max: Some(max_storage.into()) }
+    self.internal_storage_balance_bounds_batch(&token_ids, account_id)
   }
 
-  fn storage_balance_of(
-    &self,
-    token_ids: Vec<TokenId>,
-    account_id: AccountId,
-  ) -> Option<StorageBalance> {
-    self.internal_storage_balance_of_batch(&token_ids, &account_id)
+  fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+    self.internal_storage_balance_of(&account_id)
   }
 }
@@ -0,0 +1,115 @@
+use crate::enumeration::MultiTokenEnumeration;
+use crate::token::{Token, TokenId};
+use crate::MultiToken;
+use near_sdk::collections::UnorderedSet;
+use near_sdk::json_types::U128;
+use near_sdk::{env, require, AccountId};
+
+impl MultiToken {
+	// storage key prefix of the set of token ids held by account_id
+	fn tokens_per_owner_prefix(&self, account_id: &AccountId) -> Vec<u8> {
+		[self.owner_prefix.clone(), "e".into(), env::sha256(account_id.as_bytes())].concat()
+	}
+
+	/// Records that `account_id` holds `token_id`, if enumeration is used
+	pub fn internal_enumeration_add(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+	) {
+		let prefix = self.tokens_per_owner_prefix(account_id);
+		if let Some(tokens_per_owner) = &mut self.tokens_per_owner {
+			let mut token_ids = tokens_per_owner.get(account_id).unwrap_or_else(|| UnorderedSet::new(prefix));
+			if token_ids.insert(token_id) {
+				tokens_per_owner.insert(account_id, &token_ids);
+			}
+		}
+	}
+
+	/// Records that `account_id` no longer holds `token_id`, if enumeration is used
+	pub fn internal_enumeration_remove(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+	) {
+		if let Some(tokens_per_owner) = &mut self.tokens_per_owner {
+			if let Some(mut token_ids) = tokens_per_owner.get(account_id) {
+				token_ids.remove(token_id);
+				if token_ids.is_empty() {
+					tokens_per_owner.remove(account_id);
+				} else {
+					tokens_per_owner.insert(account_id, &token_ids);
+				}
+			}
+		}
+	}
+
+	/// Adds or removes `token_id` from the tokens of `account_id` depending on whether the account
+	/// still holds a balance of it
+	pub fn internal_enumeration_sync(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+		balance: u128,
+	) {
+		if balance > 0 {
+			self.internal_enumeration_add(token_id, account_id);
+		} else {
+			self.internal_enumeration_remove(token_id, account_id);
+		}
+	}
+}
+
+impl MultiTokenEnumeration for MultiToken {
+	fn mt_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token> {
+		let all_token_ids = self
+			.all_token_ids
+			.as_ref()
+			.unwrap_or_else(|| env::panic_str("Could not find all_token_ids, enumeration is not enabled"));
+		let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+		require!(all_token_ids.len() as u128 >= start_index, "Out of bounds, please use a smaller from_index.");
+		let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+		require!(limit != 0, "Cannot provide limit of 0.");
+		all_token_ids
+			.iter()
+			.skip(start_index as usize)
+			.take(limit)
+			.map(|token_id| self.internal_get_token(&token_id, None).unwrap())
+			.collect()
+	}
+
+	fn mt_tokens_for_owner(
+		&self,
+		account_id: AccountId,
+		from_index: Option<U128>,
+		limit: Option<u64>,
+	) -> Vec<Token> {
+		let tokens_per_owner = self
+			.tokens_per_owner
+			.as_ref()
+			.unwrap_or_else(|| env::panic_str("Could not find tokens_per_owner, enumeration is not enabled"));
+		let token_set = if let Some(token_set) = tokens_per_owner.get(&account_id) {
+			token_set
+		} else {
+			return vec![];
+		};
+		let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+		require!(token_set.len() as u128 >= start_index, "Out of bounds, please use a smaller from_index.");
+		let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+		require!(limit != 0, "Cannot provide limit of 0.");
+		token_set
+			.iter()
+			.skip(start_index as usize)
+			.take(limit)
+			.map(|token_id| self.internal_get_token(&token_id, Some(&account_id)).unwrap())
+			.collect()
+	}
+
+	fn mt_supply_for_owner(&self, account_id: AccountId) -> U128 {
+		let tokens_per_owner = self
+			.tokens_per_owner
+			.as_ref()
+			.unwrap_or_else(|| env::panic_str("Could not find tokens_per_owner, enumeration is not enabled"));
+		tokens_per_owner.get(&account_id).map(|token_set| token_set.len() as u128).unwrap_or(0).into()
+	}
+}
@@ -0,0 +1,45 @@
+mod enumeration_impl;
+
+use crate::token::Token;
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+
+/// Offers methods helpful in determining account ownership of multi tokens and provides a way
+/// to page through all token ids and the token ids held by an account.
+pub trait MultiTokenEnumeration {
+    /// Get a list of all tokens
+    ///
+    /// Arguments:
+    /// * `from_index`: a string representing an unsigned 128-bit integer,
+    ///    representing the starting index of tokens to return
+    /// * `limit`: the maximum number of tokens to return
+    ///
+    /// Returns an array of Token objects, as described in Core standard. FT type tokens are
+    /// returned without an owner as they can have many holders.
+    fn mt_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token>;
+
+    /// Get list of all tokens held by a given account
+    ///
+    /// Arguments:
+    /// * `account_id`: a valid NEAR account
+    /// * `from_index`: a string representing an unsigned 128-bit integer,
+    ///    representing the starting index of tokens to return
+    /// * `limit`: the maximum number of tokens to return
+    ///
+    /// Returns a paginated list of all tokens held by this account, with `account_id` as the owner
+    fn mt_tokens_for_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Token>;
+
+    /// Get the number of distinct token ids held by a given account
+    ///
+    /// Arguments:
+    /// * `account_id`: a valid NEAR account
+    ///
+    /// Returns the number of token ids for which `account_id` has a non zero balance as a
+    /// string representing an unsigned 128-bit integer to avoid JSON number limit of 2^53.
+    fn mt_supply_for_owner(&self, account_id: AccountId) -> U128;
+}
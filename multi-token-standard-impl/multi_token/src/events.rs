@@ -0,0 +1,182 @@
+//! Standard for nep245 (Multi Token) events.
+//!
+//! These events will be picked up by the NEAR indexer.
+//!
+//! <https://github.com/near/NEPs/blob/master/neps/nep-0245.md#events>
+//!
+//! This is an extension of the events format (nep-297):
+//! <https://github.com/near/NEPs/blob/master/neps/nep-0297.md>
+//!
+//! The three events in this standard are [`MtMint`], [`MtTransfer`], and [`MtBurn`].
+//! [`MtMetadataUpdate`] is logged in the same format when token metadata is updated.
+//!
+//! These events can be logged by calling `.emit()` on them if a single event, or calling
+//! [`MtMint::emit_many`], [`MtTransfer::emit_many`],
+//! or [`MtBurn::emit_many`] respectively.
+
+use crate::token::TokenId;
+use near_sdk::json_types::U128;
+use near_sdk::serde::Serialize;
+use near_sdk::AccountId;
+
+/// Data to log for a multi token mint event. To log this event, call [`.emit()`](MtMint::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtMint<'a> {
+    pub owner_id: &'a AccountId,
+    pub token_ids: &'a [TokenId],
+    pub amounts: &'a [U128],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+impl MtMint<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    /// Emits an mt mint event, through [`env::log_str`](near_sdk::env::log_str),
+    /// where each [`MtMint`] represents the data of each mint.
+    pub fn emit_many(data: &[MtMint<'_>]) {
+        new_245_v1(Nep245EventKind::MtMint(data)).emit()
+    }
+}
+
+/// Data to log for a multi token transfer event. To log this event,
+/// call [`.emit()`](MtTransfer::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtTransfer<'a> {
+    pub old_owner_id: &'a AccountId,
+    pub new_owner_id: &'a AccountId,
+    pub token_ids: &'a [TokenId],
+    pub amounts: &'a [U128],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<&'a AccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+impl MtTransfer<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    /// Emits an mt transfer event, through [`env::log_str`](near_sdk::env::log_str),
+    /// where each [`MtTransfer`] represents the data of each transfer.
+    pub fn emit_many(data: &[MtTransfer<'_>]) {
+        new_245_v1(Nep245EventKind::MtTransfer(data)).emit()
+    }
+}
+
+/// Data to log for a multi token burn event. To log this event, call [`.emit()`](MtBurn::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtBurn<'a> {
+    pub owner_id: &'a AccountId,
+    pub token_ids: &'a [TokenId],
+    pub amounts: &'a [U128],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<&'a AccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+impl MtBurn<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    /// Emits an mt burn event, through [`env::log_str`](near_sdk::env::log_str),
+    /// where each [`MtBurn`] represents the data of each burn.
+    pub fn emit_many(data: &[MtBurn<'_>]) {
+        new_245_v1(Nep245EventKind::MtBurn(data)).emit()
+    }
+}
+
+/// Data to log for a token metadata update. This is not part of nep245, but follows the same
+/// format. To log this event, call [`.emit()`](MtMetadataUpdate::emit).
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtMetadataUpdate<'a> {
+    pub token_ids: &'a [TokenId],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+impl MtMetadataUpdate<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    /// Emits an mt metadata update event, through [`env::log_str`](near_sdk::env::log_str),
+    /// where each [`MtMetadataUpdate`] represents the data of each update.
+    pub fn emit_many(data: &[MtMetadataUpdate<'_>]) {
+        new_245_v1(Nep245EventKind::MtMetadataUpdate(data)).emit()
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "standard")]
+#[serde(rename_all = "snake_case")]
+enum NearEvent<'a> {
+    Nep245(Nep245Event<'a>),
+}
+
+impl<'a> NearEvent<'a> {
+    fn to_json_string(&self) -> String {
+        // Events cannot fail to serialize so fine to panic on error
+        near_sdk::serde_json::to_string(self).unwrap_or_else(|_| near_sdk::env::abort())
+    }
+
+    fn to_json_event_string(&self) -> String {
+        format!("EVENT_JSON:{}", self.to_json_string())
+    }
+
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    fn emit(self) {
+        near_sdk::env::log_str(&self.to_json_event_string());
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+struct Nep245Event<'a> {
+    version: &'static str,
+    #[serde(flatten)]
+    event_kind: Nep245EventKind<'a>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)]
+enum Nep245EventKind<'a> {
+    MtMint(&'a [MtMint<'a>]),
+    MtTransfer(&'a [MtTransfer<'a>]),
+    MtBurn(&'a [MtBurn<'a>]),
+    MtMetadataUpdate(&'a [MtMetadataUpdate<'a>]),
+}
+
+fn new_245<'a>(version: &'static str, event_kind: Nep245EventKind<'a>) -> NearEvent<'a> {
+    NearEvent::Nep245(Nep245Event { version, event_kind })
+}
+
+fn new_245_v1(event_kind: Nep245EventKind) -> NearEvent {
+    new_245("1.0.0", event_kind)
+}
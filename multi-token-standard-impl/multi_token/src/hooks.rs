@@ -0,0 +1,56 @@
+use crate::token::TokenId;
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+
+/// Lets the contract wrapping a `MultiToken` veto or react to tokens being moved through the
+/// methods implemented by the `impl_multi_token_*!` macros, including balances burned by a forced
+/// `storage_unregister`. Transfers and burns the contract makes itself are only seen through the
+/// `mt_internal_transfer` and `mt_internal_burn` methods `impl_multi_token_core!` adds to it.
+/// Panicking in a `before_*` hook aborts the call.
+///
+/// Every hook does nothing by default, so `impl MultiTokenHooks for Contract {}` is enough for
+/// contracts that have no transfer policy.
+///
+/// Arguments shared by the hooks:
+/// * `sender_id`: the account calling the method, either the owner of the tokens or an
+///   account approved by them
+/// * `owner_ids`: the owner of each token the tokens are taken from
+/// * `token_ids` and `amounts`: the tokens moved in a 1-1 mapping
+///
+/// Tokens given back by `mt_resolve_transfer` are passed to `after_transfer` only, one token at a
+/// time, with the receiver of the `_call` transfer as the sender and owner. Refunds can't be
+/// vetoed.
+pub trait MultiTokenHooks {
+    /// Called before tokens are transferred to `receiver_id`
+    fn before_transfer(
+        &mut self,
+        _sender_id: &AccountId,
+        _owner_ids: &[AccountId],
+        _receiver_id: &AccountId,
+        _token_ids: &[TokenId],
+        _amounts: &[U128],
+    ) {
+    }
+
+    /// Called once tokens are transferred to `receiver_id`, before `mt_on_transfer` is called
+    /// on the receiver for the `_call` variants
+    fn after_transfer(
+        &mut self,
+        _sender_id: &AccountId,
+        _owner_ids: &[AccountId],
+        _receiver_id: &AccountId,
+        _token_ids: &[TokenId],
+        _amounts: &[U128],
+    ) {
+    }
+
+    /// Called before tokens are burned
+    fn before_burn(
+        &mut self,
+        _sender_id: &AccountId,
+        _owner_ids: &[AccountId],
+        _token_ids: &[TokenId],
+        _amounts: &[U128],
+    ) {
+    }
+}
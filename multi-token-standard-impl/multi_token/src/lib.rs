@@ -1,8 +1,27 @@
+/// Trait for the [MT approval management standard](https://nomicon.io/Standards/MultiToken/ApprovalManagement).
+pub mod approval;
 /// The [core semifungible token standard](). This can be though of as the base standard, with the others being extension standards.
 pub mod core;
+/// Standard events emitted on mint, burn and transfer, following [NEP-297](https://nomicon.io/Standards/EventsFormat).
+pub mod events;
+/// Balances and supply of FT type tokens at snapshots, for voting and payouts based on holdings.
+pub mod checkpoint;
+/// Hooks letting the contract wrapping a `MultiToken` veto or react to tokens being moved.
+pub mod hooks;
+/// Trait for the [MT enumeration standard](https://nomicon.io/Standards/MultiToken/Enumeration).
+/// This provides useful view-only methods returning token supply, tokens by owner, etc.
+pub mod enumeration;
+/// Locks on balances of FT type tokens, held by escrow or lending contracts without taking custody.
+pub mod lock;
 /// Metadata traits and implementation according to the [NFT enumeration standard](https://nomicon.io/Standards/NonFungibleToken/Metadata.html).
 /// This covers both the contract metadata and the individual token metadata.
 pub mod metadata;
+/// Royalties set at mint and the [NEP-199](https://nomicon.io/Standards/NonFungibleToken/Payout) style payouts honoring them.
+pub mod payout;
+/// Transfers signed off-chain by their owner and submitted by any relayer.
+pub mod permit;
+/// Trait for freezing the transfers of token ids and pausing every token movement.
+pub mod pause;
 /// This covers the storage management for the tokens
 pub mod storage_management;
 /// The Token struct for the MultiToken token standard.
@@ -12,5 +31,5 @@ mod macros;
 mod utils;
 
 pub use self::core::MultiToken;
-pub use self::token::{Token, TokenId, TokenType};
+pub use self::token::{Token, TokenId, TokenSupply, TokenType};
 pub use macros::*;
@@ -0,0 +1,199 @@
+use crate::events::MtTransfer;
+use crate::lock::{Lock, MultiTokenLock};
+use crate::token::{TokenId, TokenType};
+use crate::utils::{assert_at_least_one_yocto, refund_deposit};
+use crate::MultiToken;
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{assert_one_yocto, env, require, AccountId, Balance};
+
+impl MultiToken {
+	/// The amount of `token_id` held by `account_id` under locks that have not expired
+	pub fn internal_locked_balance_of(
+		&self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+	) -> Balance {
+		let now = env::block_timestamp();
+		self.ft_locks
+			.get(&(token_id.clone(), account_id.clone()))
+			.unwrap_or_default()
+			.iter()
+			.filter(|lock| is_active(lock, now))
+			.map(|lock| lock.amount.0)
+			.sum()
+	}
+
+	/// Panics unless `account_id` holds at least `amount` of `token_id` outside of its locks.
+	/// Expired locks are removed on the way.
+	pub fn assert_unlocked_balance(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+		amount: Balance,
+	) {
+		self.internal_remove_expired_locks(token_id, account_id);
+		let balance = self
+			.ft_owners_by_id
+			.get(token_id)
+			.and_then(|balances| balances.get(account_id))
+			.unwrap_or(0);
+		if balance.saturating_sub(self.internal_locked_balance_of(token_id, account_id)) < amount {
+			env::panic_str("Amount exceeds the unlocked balance");
+		}
+	}
+
+	/// Removes the expired locks on the `token_id` of `account_id`, refunding the storage they
+	/// took to the account, which paid for it when locking.
+	pub(crate) fn internal_remove_expired_locks(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		account_id: &AccountId,
+	) {
+		let key = (token_id.clone(), account_id.clone());
+		let locks = match self.ft_locks.get(&key) {
+			Some(locks) => locks,
+			None => return,
+		};
+		let now = env::block_timestamp();
+		let active: Vec<Lock> = locks.iter().filter(|lock| is_active(lock, now)).cloned().collect();
+		if active.len() == locks.len() {
+			return;
+		}
+		let initial_storage_usage = env::storage_usage();
+		if active.is_empty() {
+			self.ft_locks.remove(&key);
+		} else {
+			self.ft_locks.insert(&key, &active);
+		}
+		Self::refund_released_storage(account_id.clone(), initial_storage_usage);
+	}
+
+	/// Takes `amount`, or everything when not given, out of the lock `locker_id` holds on the
+	/// `token_id` of `owner_id`. Returns the amount taken.
+	fn internal_use_lock(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		owner_id: &AccountId,
+		locker_id: &AccountId,
+		amount: Option<Balance>,
+		active_only: bool,
+	) -> Balance {
+		let key = (token_id.clone(), owner_id.clone());
+		let mut locks = self.ft_locks.get(&key).unwrap_or_default();
+		let idx = locks.iter().position(|lock| &lock.locker_id == locker_id).unwrap_or_else(|| {
+			env::panic_str(
+				format!("{} has no lock on the {} of {}", locker_id, token_id, owner_id).as_str(),
+			)
+		});
+		if active_only && !is_active(&locks[idx], env::block_timestamp()) {
+			env::panic_str("The lock has expired");
+		}
+		let locked = locks[idx].amount.0;
+		let amount = amount.unwrap_or(locked);
+		require!(amount <= locked, "Amount exceeds the locked amount");
+		if amount == locked {
+			locks.remove(idx);
+		} else {
+			locks[idx].amount = (locked - amount).into();
+		}
+		if locks.is_empty() {
+			self.ft_locks.remove(&key);
+		} else {
+			self.ft_locks.insert(&key, &locks);
+		}
+		amount
+	}
+}
+
+fn is_active(lock: &Lock, now: u64) -> bool {
+	lock.expires_at.map(|expires_at| expires_at.0 > now).unwrap_or(true)
+}
+
+/// The later of two expiries, where no expiry is the latest
+fn later(a: Option<U64>, b: Option<U64>) -> Option<U64> {
+	match (a, b) {
+		(Some(a), Some(b)) => Some(std::cmp::max(a.0, b.0).into()),
+		_ => None,
+	}
+}
+
+impl MultiTokenLock for MultiToken {
+	fn mt_lock(
+		&mut self,
+		token_id: TokenId,
+		amount: U128,
+		locker_id: AccountId,
+		expires_at: Option<U64>,
+	) {
+		assert_at_least_one_yocto();
+		require!(
+			self.token_type_index.get(&token_id) == Some(TokenType::Ft),
+			"Only FT type tokens can be locked"
+		);
+		require!(amount.0 > 0, "The amount should be a positive number");
+		let owner_id = env::predecessor_account_id();
+		require!(owner_id != locker_id, "The owner cannot lock tokens for itself");
+		let now = env::block_timestamp();
+		if let Some(expires_at) = expires_at {
+			require!(expires_at.0 > now, "The lock must expire in the future");
+		}
+		self.assert_unlocked_balance(&token_id, &owner_id, amount.0);
+
+		let initial_storage_usage = env::storage_usage();
+		let key = (token_id, owner_id);
+		let mut locks = self.ft_locks.get(&key).unwrap_or_default();
+		match locks.iter_mut().find(|lock| lock.locker_id == locker_id) {
+			// The holder cannot shorten a lock by adding to it
+			Some(lock) => {
+				lock.amount = (lock.amount.0 + amount.0).into();
+				lock.expires_at = later(lock.expires_at, expires_at);
+			}
+			None => locks.push(Lock { locker_id, amount, expires_at }),
+		}
+		self.ft_locks.insert(&key, &locks);
+		refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+	}
+
+	fn mt_unlock(&mut self, token_id: TokenId, owner_id: AccountId, amount: Option<U128>) {
+		assert_one_yocto();
+		let initial_storage_usage = env::storage_usage();
+		let locker_id = env::predecessor_account_id();
+		let amount = amount.map(|amount| amount.0);
+		self.internal_use_lock(&token_id, &owner_id, &locker_id, amount, false);
+		Self::refund_released_storage(owner_id, initial_storage_usage);
+	}
+
+	fn mt_seize(
+		&mut self,
+		token_id: TokenId,
+		owner_id: AccountId,
+		amount: U128,
+		receiver_id: Option<AccountId>,
+		memo: Option<String>,
+	) {
+		assert_one_yocto();
+		let locker_id = env::predecessor_account_id();
+		let receiver_id = receiver_id.unwrap_or_else(|| locker_id.clone());
+		require!(owner_id != receiver_id, "Sender and receiver cannot be the same");
+		self.assert_transferable(&token_id);
+		self.internal_use_lock(&token_id, &owner_id, &locker_id, Some(amount.0), true);
+		self.internal_transfer_unguarded(&token_id, amount.0, &owner_id, &receiver_id);
+		MtTransfer {
+			old_owner_id: &owner_id,
+			new_owner_id: &receiver_id,
+			token_ids: &[token_id],
+			amounts: &[amount],
+			authorized_id: Some(&locker_id),
+			memo: memo.as_deref(),
+		}
+		.emit();
+	}
+
+	fn mt_locked_balance_of(&self, account_id: AccountId, token_id: TokenId) -> U128 {
+		self.internal_locked_balance_of(&token_id, &account_id).into()
+	}
+
+	fn mt_locks(&self, account_id: AccountId, token_id: TokenId) -> Vec<Lock> {
+		self.ft_locks.get(&(token_id, account_id)).unwrap_or_default()
+	}
+}
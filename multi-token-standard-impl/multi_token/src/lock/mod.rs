@@ -0,0 +1,58 @@
+mod lock_impl;
+
+pub use self::lock_impl::*;
+
+use crate::token::TokenId;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+/// Part of the balance of an FT type token which its holder cannot move until the locker releases
+/// it or the lock expires.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Lock {
+    pub locker_id: AccountId,
+    pub amount: U128,
+    /// Block timestamp in nanoseconds after which the lock no longer applies
+    pub expires_at: Option<U64>,
+}
+
+/// Lets holders lock part of their FT type tokens in place for another account, ex. a lending or
+/// escrow contract, instead of handing over custody. Locked amounts cannot be transferred, burned
+/// or dropped by unregistering, only the locker can release them or seize them.
+pub trait MultiTokenLock {
+    /// Locks `amount` of the caller's `token_id` for `locker_id`, adding to any lock it already
+    /// holds, which then keeps the later of the two expiries. The caller must attach enough
+    /// deposit to cover the storage used.
+    fn mt_lock(
+        &mut self,
+        token_id: TokenId,
+        amount: U128,
+        locker_id: AccountId,
+        expires_at: Option<U64>,
+    );
+
+    /// Releases `amount`, or all when not given, of the lock the caller holds on the `token_id`
+    /// of `owner_id`. Requires 1 yoctoⓃ attached.
+    fn mt_unlock(&mut self, token_id: TokenId, owner_id: AccountId, amount: Option<U128>);
+
+    /// Transfers `amount` out of the lock the caller holds on the `token_id` of `owner_id` to
+    /// `receiver_id`, defaulting to the caller. The lock must not have expired. Requires
+    /// 1 yoctoⓃ attached.
+    fn mt_seize(
+        &mut self,
+        token_id: TokenId,
+        owner_id: AccountId,
+        amount: U128,
+        receiver_id: Option<AccountId>,
+        memo: Option<String>,
+    );
+
+    /// The amount of `token_id` held by `account_id` under locks that have not expired
+    fn mt_locked_balance_of(&self, account_id: AccountId, token_id: TokenId) -> U128;
+
+    /// The locks on the `token_id` of `account_id`, including expired ones not yet cleared
+    fn mt_locks(&self, account_id: AccountId, token_id: TokenId) -> Vec<Lock>;
+}
@@ -1,6 +1,10 @@
 /// The core methods for a basic semi fungible token. Extension standards may be
 /// added in addition to this macro.
 ///
+/// `$contract` must implement [`MultiTokenHooks`](crate::hooks::MultiTokenHooks), which the
+/// transfer and burn methods call into. The `MultiToken` itself can't reach the contract, so
+/// transfers and burns the contract makes on its own should go through the generated
+/// `mt_internal_transfer` and `mt_internal_burn` for the hooks to see them.
 
 #[macro_export]
 macro_rules! impl_multi_token_core {
@@ -16,9 +20,30 @@ macro_rules! impl_multi_token_core {
                 receiver_id: AccountId,
                 token_id: $crate::TokenId,
                 amount: U128,
+                approval: Option<(AccountId, u64)>,
                 memo: Option<String>,
             ) {
-                self.$token.mt_transfer(receiver_id, token_id, amount, memo)
+                let sender_id = env::predecessor_account_id();
+                let owner_ids =
+                    vec![self.$token.internal_token_owner(&token_id, &sender_id, approval.as_ref())];
+                let (token_ids, amounts) = (vec![token_id.clone()], vec![amount]);
+                $crate::hooks::MultiTokenHooks::before_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+                self.$token.mt_transfer(receiver_id.clone(), token_id, amount, approval, memo);
+                $crate::hooks::MultiTokenHooks::after_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
             }
 
             #[payable]
@@ -27,10 +52,33 @@ macro_rules! impl_multi_token_core {
                 receiver_id: AccountId,
                 token_id: $crate::TokenId,
                 amount: U128,
+                approval: Option<(AccountId, u64)>,
                 memo: Option<String>,
                 msg: String,
             ) -> PromiseOrValue<U128> {
-                self.$token.mt_transfer_call(receiver_id, token_id, amount, memo, msg)
+                let sender_id = env::predecessor_account_id();
+                let owner_ids =
+                    vec![self.$token.internal_token_owner(&token_id, &sender_id, approval.as_ref())];
+                let (token_ids, amounts) = (vec![token_id.clone()], vec![amount]);
+                $crate::hooks::MultiTokenHooks::before_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+                let promise =
+                    self.$token.mt_transfer_call(receiver_id.clone(), token_id, amount, approval, memo, msg);
+                $crate::hooks::MultiTokenHooks::after_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+                promise
             }
 
             #[payable]
@@ -39,9 +87,35 @@ macro_rules! impl_multi_token_core {
                 receiver_id: AccountId,
                 token_id: Vec<$crate::TokenId>,
                 amounts: Vec<U128>,
+                approvals: Option<Vec<Option<(AccountId, u64)>>>,
                 memo: Option<String>,
             ) {
-                self.$token.mt_batch_transfer(receiver_id, token_id, amounts, memo)
+                let sender_id = env::predecessor_account_id();
+                let owner_ids =
+                    self.$token.internal_token_owners(&token_id, &sender_id, approvals.as_ref());
+                $crate::hooks::MultiTokenHooks::before_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_id,
+                    &amounts,
+                );
+                self.$token.mt_batch_transfer(
+                    receiver_id.clone(),
+                    token_id.clone(),
+                    amounts.clone(),
+                    approvals,
+                    memo,
+                );
+                $crate::hooks::MultiTokenHooks::after_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_id,
+                    &amounts,
+                );
             }
 
             #[payable]
@@ -50,10 +124,80 @@ macro_rules! impl_multi_token_core {
                 receiver_id: AccountId,
                 token_ids: Vec<$crate::TokenId>,
                 amounts: Vec<U128>,
+                approvals: Option<Vec<Option<(AccountId, u64)>>>,
                 memo: Option<String>,
                 msg: String,
             ) -> PromiseOrValue<Vec<U128>> {
-                self.$token.mt_batch_transfer_call(receiver_id, token_ids, amounts, memo, msg)
+                let sender_id = env::predecessor_account_id();
+                let owner_ids =
+                    self.$token.internal_token_owners(&token_ids, &sender_id, approvals.as_ref());
+                $crate::hooks::MultiTokenHooks::before_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+                let promise = self.$token.mt_batch_transfer_call(
+                    receiver_id.clone(),
+                    token_ids.clone(),
+                    amounts.clone(),
+                    approvals,
+                    memo,
+                    msg,
+                );
+                $crate::hooks::MultiTokenHooks::after_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+                promise
+            }
+
+            #[payable]
+            fn mt_burn(
+                &mut self,
+                token_id: $crate::TokenId,
+                amount: U128,
+                approval: Option<(AccountId, u64)>,
+                memo: Option<String>,
+            ) {
+                let sender_id = env::predecessor_account_id();
+                let owner_ids =
+                    vec![self.$token.internal_token_owner(&token_id, &sender_id, approval.as_ref())];
+                $crate::hooks::MultiTokenHooks::before_burn(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &[token_id.clone()],
+                    &[amount],
+                );
+                self.$token.mt_burn(token_id, amount, approval, memo)
+            }
+
+            #[payable]
+            fn mt_batch_burn(
+                &mut self,
+                token_ids: Vec<$crate::TokenId>,
+                amounts: Vec<U128>,
+                approvals: Option<Vec<Option<(AccountId, u64)>>>,
+                memo: Option<String>,
+            ) {
+                let sender_id = env::predecessor_account_id();
+                let owner_ids =
+                    self.$token.internal_token_owners(&token_ids, &sender_id, approvals.as_ref());
+                $crate::hooks::MultiTokenHooks::before_burn(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &token_ids,
+                    &amounts,
+                );
+                self.$token.mt_batch_burn(token_ids, amounts, approvals, memo)
             }
 
             fn balance_of(&self, owner_id: AccountId, token_id: $crate::TokenId) -> U128 {
@@ -68,6 +212,22 @@ macro_rules! impl_multi_token_core {
                 self.$token.balance_of_batch(owner_id, token_ids)
             }
 
+            fn balance_of_accounts(
+                &self,
+                account_ids: Vec<AccountId>,
+                token_id: $crate::TokenId,
+            ) -> Vec<U128> {
+                self.$token.balance_of_accounts(account_ids, token_id)
+            }
+
+            fn balance_of_accounts_batch(
+                &self,
+                account_ids: Vec<AccountId>,
+                token_ids: Vec<$crate::TokenId>,
+            ) -> Vec<Vec<U128>> {
+                self.$token.balance_of_accounts_batch(account_ids, token_ids)
+            }
+
             fn total_supply(&self, token_id: $crate::TokenId) -> U128 {
                 self.$token.total_supply(token_id)
             }
@@ -75,6 +235,18 @@ macro_rules! impl_multi_token_core {
             fn total_supply_batch(&self, token_ids: Vec<$crate::TokenId>) -> Vec<U128> {
                 self.$token.total_supply_batch(token_ids)
             }
+
+            fn mt_supply(&self, token_id: $crate::TokenId) -> $crate::TokenSupply {
+                self.$token.mt_supply(token_id)
+            }
+
+            fn mt_token(&self, token_id: $crate::TokenId) -> Option<$crate::Token> {
+                self.$token.mt_token(token_id)
+            }
+
+            fn mt_token_batch(&self, token_ids: Vec<$crate::TokenId>) -> Vec<Option<$crate::Token>> {
+                self.$token.mt_token_batch(token_ids)
+            }
         }
 
         #[near_bindgen]
@@ -82,31 +254,239 @@ macro_rules! impl_multi_token_core {
             #[private]
             fn mt_resolve_transfer(
                 &mut self,
-                sender_id: AccountId,
+                previous_owner_ids: Vec<AccountId>,
                 receiver_id: AccountId,
                 token_ids: Vec<$crate::TokenId>,
                 amounts: Vec<U128>,
+                approvals: Option<Vec<Option<(AccountId, u64, U128)>>>,
             ) -> Vec<U128> {
-                self.$token.mt_resolve_transfer(sender_id, receiver_id, token_ids, amounts)
+                let (resolved_amounts, refunds) = self.$token.internal_resolve_transfer(
+                    previous_owner_ids,
+                    receiver_id.clone(),
+                    token_ids,
+                    amounts,
+                    approvals,
+                );
+                let owner_ids = vec![receiver_id.clone()];
+                for (previous_owner_id, token_id, amount) in refunds {
+                    $crate::hooks::MultiTokenHooks::after_transfer(
+                        self,
+                        &receiver_id,
+                        &owner_ids,
+                        &previous_owner_id,
+                        &[token_id],
+                        &[amount],
+                    );
+                }
+                resolved_amounts
+            }
+        }
+
+        impl $contract {
+            /// Transfers `amount` of `token_id` from `owner_id` to `receiver_id` on behalf of the
+            /// contract, running the hooks like `mt_transfer` does
+            #[allow(clippy::ptr_arg)]
+            pub fn mt_internal_transfer(
+                &mut self,
+                owner_id: &AccountId,
+                receiver_id: &AccountId,
+                token_id: &$crate::TokenId,
+                amount: near_sdk::Balance,
+                memo: Option<String>,
+            ) {
+                let owner_ids = vec![owner_id.clone()];
+                let (token_ids, amounts) = (vec![token_id.clone()], vec![U128::from(amount)]);
+                $crate::hooks::MultiTokenHooks::before_transfer(
+                    self,
+                    owner_id,
+                    &owner_ids,
+                    receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+                self.$token.internal_transfer(owner_id, receiver_id, token_id, amount, None, memo);
+                $crate::hooks::MultiTokenHooks::after_transfer(
+                    self,
+                    owner_id,
+                    &owner_ids,
+                    receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+            }
+
+            /// Burns `amount` of `token_id` owned by `owner_id` on behalf of the contract, running
+            /// the hooks like `mt_burn` does
+            #[allow(clippy::ptr_arg)]
+            pub fn mt_internal_burn(
+                &mut self,
+                token_id: &$crate::TokenId,
+                owner_id: &AccountId,
+                amount: near_sdk::Balance,
+                memo: Option<String>,
+            ) {
+                $crate::hooks::MultiTokenHooks::before_burn(
+                    self,
+                    owner_id,
+                    &[owner_id.clone()],
+                    &[token_id.clone()],
+                    &[U128::from(amount)],
+                );
+                self.$token.internal_burn(token_id, owner_id, amount, memo);
             }
         }
     };
 }
 
+/// Implements the metadata views and updates. Contract level metadata is kept on the contract in a
+/// `LazyOption<MtContractMetadata>` field, token and base metadata are kept by the `MultiToken`.
 #[macro_export]
 macro_rules! impl_multi_token_metadata {
-    ($contract: ident, $token: ident) => {
-        use $crate::metadata::MultiTokenMetadataProvider;
+    ($contract: ident, $token: ident, $metadata: ident) => {
+        use $crate::metadata::{MultiTokenMetadataProvider, MultiTokenMetadataUpdate};
 
         #[near_bindgen]
         impl MultiTokenMetadataProvider for $contract {
-            fn mt_metadata(&self, token_id: String) -> $crate::metadata::MultiTokenMetadata {
-                self.$token
-                    .token_metadata_by_id
-                    .as_ref()
-                    .unwrap()
-                    .get(&token_id)
-                    .expect("Cannot find the metadata for the given token")
+            fn mt_metadata_contract(&self) -> $crate::metadata::MtContractMetadata {
+                self.$metadata.get().unwrap()
+            }
+
+            fn mt_metadata_token_all(
+                &self,
+                token_ids: Vec<$crate::TokenId>,
+            ) -> Vec<Option<$crate::metadata::TokenMetadataAll>> {
+                self.$token.mt_metadata_token_all(token_ids)
+            }
+
+            fn mt_metadata_token_by_token_id(
+                &self,
+                token_ids: Vec<$crate::TokenId>,
+            ) -> Vec<Option<$crate::metadata::TokenMetadata>> {
+                self.$token.mt_metadata_token_by_token_id(token_ids)
+            }
+
+            fn mt_metadata_base_by_token_id(
+                &self,
+                token_ids: Vec<$crate::TokenId>,
+            ) -> Vec<Option<$crate::metadata::BaseTokenMetadata>> {
+                self.$token.mt_metadata_base_by_token_id(token_ids)
+            }
+
+            fn mt_metadata_base_by_metadata_id(
+                &self,
+                base_metadata_ids: Vec<String>,
+            ) -> Vec<Option<$crate::metadata::BaseTokenMetadata>> {
+                self.$token.mt_metadata_base_by_metadata_id(base_metadata_ids)
+            }
+        }
+
+        #[near_bindgen]
+        impl MultiTokenMetadataUpdate for $contract {
+            #[payable]
+            fn mt_update_metadata(
+                &mut self,
+                token_id: $crate::TokenId,
+                patch: $crate::metadata::TokenMetadataPatch,
+                memo: Option<String>,
+            ) {
+                self.$token.mt_update_metadata(token_id, patch, memo)
+            }
+
+            #[payable]
+            fn mt_freeze_metadata(&mut self, token_id: $crate::TokenId) {
+                self.$token.mt_freeze_metadata(token_id)
+            }
+
+            fn mt_is_metadata_frozen(&self, token_id: $crate::TokenId) -> bool {
+                self.$token.mt_is_metadata_frozen(token_id)
+            }
+        }
+    };
+}
+
+/// Multi token approval management allows for an escrow system where
+/// multiple approvals per token and per owner exist.
+#[macro_export]
+macro_rules! impl_multi_token_approval {
+    ($contract: ident, $token: ident) => {
+        use $crate::approval::MultiTokenApproval;
+
+        #[near_bindgen]
+        impl MultiTokenApproval for $contract {
+            #[payable]
+            fn mt_approve(
+                &mut self,
+                token_ids: Vec<$crate::TokenId>,
+                amounts: Vec<U128>,
+                account_id: AccountId,
+                msg: Option<String>,
+            ) -> Option<near_sdk::Promise> {
+                self.$token.mt_approve(token_ids, amounts, account_id, msg)
+            }
+
+            #[payable]
+            fn mt_revoke(&mut self, token_ids: Vec<$crate::TokenId>, account_id: AccountId) {
+                self.$token.mt_revoke(token_ids, account_id)
+            }
+
+            #[payable]
+            fn mt_revoke_all(&mut self, token_ids: Vec<$crate::TokenId>) {
+                self.$token.mt_revoke_all(token_ids)
+            }
+
+            fn mt_is_approved(
+                &self,
+                owner_id: AccountId,
+                token_ids: Vec<$crate::TokenId>,
+                approved_account_id: AccountId,
+                amounts: Vec<U128>,
+                approval_ids: Option<Vec<u64>>,
+            ) -> bool {
+                self.$token.mt_is_approved(
+                    owner_id,
+                    token_ids,
+                    approved_account_id,
+                    amounts,
+                    approval_ids,
+                )
+            }
+
+            #[payable]
+            fn mt_set_operator(&mut self, operator_id: AccountId, approved: bool) {
+                self.$token.mt_set_operator(operator_id, approved)
+            }
+
+            fn mt_is_operator(&self, owner_id: AccountId, operator_id: AccountId) -> bool {
+                self.$token.mt_is_operator(owner_id, operator_id)
+            }
+        }
+    };
+}
+
+/// Multi token enumeration adds the extension standard offering several
+/// view-only methods to get token supply, tokens per owner, etc.
+#[macro_export]
+macro_rules! impl_multi_token_enumeration {
+    ($contract: ident, $token: ident) => {
+        use $crate::enumeration::MultiTokenEnumeration;
+
+        #[near_bindgen]
+        impl MultiTokenEnumeration for $contract {
+            fn mt_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<$crate::Token> {
+                self.$token.mt_tokens(from_index, limit)
+            }
+
+            fn mt_tokens_for_owner(
+                &self,
+                account_id: AccountId,
+                from_index: Option<U128>,
+                limit: Option<u64>,
+            ) -> Vec<$crate::Token> {
+                self.$token.mt_tokens_for_owner(account_id, from_index, limit)
+            }
+
+            fn mt_supply_for_owner(&self, account_id: AccountId) -> U128 {
+                self.$token.mt_supply_for_owner(account_id)
             }
         }
     };
@@ -116,7 +496,8 @@ macro_rules! impl_multi_token_metadata {
 /// the storage is be paid by the caller. This ensures that storage cannot grow to a point
 /// that the MT contract runs out of Ⓝ.
 /// Takes name of the Contract struct, the inner field for the token and optional method name to
-/// call when the account was closed.
+/// call when the account was closed. Balances burned by a forced unregister are passed to the
+/// `before_burn` hook of `$contract`.
 #[macro_export]
 macro_rules! impl_multi_token_storage {
     ($contract: ident, $token: ident $(, $on_account_closed_fn:ident)?) => {
@@ -136,12 +517,26 @@ macro_rules! impl_multi_token_storage {
                 self.$token.storage_deposit(token_ids, account_id, registration_only)
             }
             #[payable]
-            fn storage_withdraw(&mut self, token_ids:Vec<$crate::TokenId>, amount: Option<U128>) -> StorageBalance {
-                self.$token.storage_withdraw(token_ids, amount)
+            fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+                self.$token.storage_withdraw(amount)
             }
 
             #[payable]
             fn storage_unregister(&mut self, token_ids: Vec<$crate::TokenId>, force: Option<bool>) -> Vec<bool> {
+                if force.unwrap_or(false) {
+                    let owner_id = env::predecessor_account_id();
+                    let (burned_ids, burned_amounts) =
+                        self.$token.internal_storage_forced_burns(&token_ids, &owner_id);
+                    if !burned_ids.is_empty() {
+                        $crate::hooks::MultiTokenHooks::before_burn(
+                            self,
+                            &owner_id,
+                            &vec![owner_id.clone(); burned_ids.len()],
+                            &burned_ids,
+                            &burned_amounts,
+                        );
+                    }
+                }
                #[allow(unused_variables)]
                let final_states = self.$token.internal_storage_unregister_batch(token_ids, force);
                 final_states.iter().map(|final_state|{
@@ -158,8 +553,284 @@ macro_rules! impl_multi_token_storage {
                 self.$token.internal_storage_balance_bounds_batch(&token_ids, account_id)
             }
 
-            fn storage_balance_of(&self, token_ids: Vec<$crate::TokenId>, account_id: AccountId) -> Option<StorageBalance> {
-                self.$token.internal_storage_balance_of_batch(&token_ids, &account_id)
+            fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+                self.$token.internal_storage_balance_of(&account_id)
+            }
+        }
+    };
+}
+
+/// Exposes freezing the transfers of token ids and pausing every token movement, gated on the
+/// owner of the `MultiToken`.
+#[macro_export]
+macro_rules! impl_multi_token_pause {
+    ($contract: ident, $token: ident) => {
+        use $crate::pause::MultiTokenPause;
+
+        #[near_bindgen]
+        impl MultiTokenPause for $contract {
+            #[payable]
+            fn mt_freeze_transfers(&mut self, token_ids: Vec<$crate::TokenId>) {
+                self.$token.mt_freeze_transfers(token_ids)
+            }
+
+            #[payable]
+            fn mt_unfreeze_transfers(&mut self, token_ids: Vec<$crate::TokenId>) {
+                self.$token.mt_unfreeze_transfers(token_ids)
+            }
+
+            #[payable]
+            fn mt_pause(&mut self) {
+                self.$token.mt_pause()
+            }
+
+            #[payable]
+            fn mt_unpause(&mut self) {
+                self.$token.mt_unpause()
+            }
+
+            fn mt_is_paused(&self) -> bool {
+                self.$token.mt_is_paused()
+            }
+
+            fn mt_is_transfer_frozen(&self, token_id: $crate::TokenId) -> bool {
+                self.$token.mt_is_transfer_frozen(token_id)
+            }
+
+            fn mt_transfer_frozen_token_ids(
+                &self,
+                from_index: Option<U128>,
+                limit: Option<u64>,
+            ) -> Vec<$crate::TokenId> {
+                self.$token.mt_transfer_frozen_token_ids(from_index, limit)
+            }
+        }
+    };
+}
+
+/// Exposes snapshots of FT type tokens, taken by the owner of the `MultiToken`, and the balances
+/// and supply at each of them.
+#[macro_export]
+macro_rules! impl_multi_token_checkpoint {
+    ($contract: ident, $token: ident) => {
+        use $crate::checkpoint::MultiTokenCheckpoint;
+
+        #[near_bindgen]
+        impl MultiTokenCheckpoint for $contract {
+            #[payable]
+            fn mt_snapshot(&mut self, token_id: $crate::TokenId) -> u64 {
+                self.$token.mt_snapshot(token_id)
+            }
+
+            fn mt_snapshot_id(&self, token_id: $crate::TokenId) -> u64 {
+                self.$token.mt_snapshot_id(token_id)
+            }
+
+            fn balance_of_at(
+                &self,
+                account_id: AccountId,
+                token_id: $crate::TokenId,
+                snapshot_id: u64,
+            ) -> U128 {
+                self.$token.balance_of_at(account_id, token_id, snapshot_id)
+            }
+
+            fn total_supply_at(&self, token_id: $crate::TokenId, snapshot_id: u64) -> U128 {
+                self.$token.total_supply_at(token_id, snapshot_id)
+            }
+        }
+    };
+}
+
+/// Exposes the payouts of tokens, splitting sale prices between their royalties and owners.
+#[macro_export]
+macro_rules! impl_multi_token_payout {
+    ($contract: ident, $token: ident) => {
+        use $crate::payout::{MultiTokenPayout, Payout};
+
+        #[near_bindgen]
+        impl MultiTokenPayout for $contract {
+            fn mt_payout(
+                &self,
+                token_id: $crate::TokenId,
+                amount: U128,
+                balance: U128,
+                max_len_payout: Option<u32>,
+            ) -> Payout {
+                self.$token.mt_payout(token_id, amount, balance, max_len_payout)
+            }
+
+            #[payable]
+            fn mt_transfer_payout(
+                &mut self,
+                receiver_id: AccountId,
+                token_id: $crate::TokenId,
+                amount: U128,
+                approval: Option<(AccountId, u64)>,
+                memo: Option<String>,
+                balance: U128,
+                max_len_payout: Option<u32>,
+            ) -> Payout {
+                let sender_id = env::predecessor_account_id();
+                let owner_ids =
+                    vec![self.$token.internal_token_owner(&token_id, &sender_id, approval.as_ref())];
+                let (token_ids, amounts) = (vec![token_id.clone()], vec![amount]);
+                $crate::hooks::MultiTokenHooks::before_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+                let payout = self.$token.mt_transfer_payout(
+                    receiver_id.clone(),
+                    token_id,
+                    amount,
+                    approval,
+                    memo,
+                    balance,
+                    max_len_payout,
+                );
+                $crate::hooks::MultiTokenHooks::after_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+                payout
+            }
+        }
+    };
+}
+
+/// Exposes locking balances for other accounts, which can release or seize them.
+#[macro_export]
+macro_rules! impl_multi_token_lock {
+    ($contract: ident, $token: ident) => {
+        use $crate::lock::{Lock, MultiTokenLock};
+
+        #[near_bindgen]
+        impl MultiTokenLock for $contract {
+            #[payable]
+            fn mt_lock(
+                &mut self,
+                token_id: $crate::TokenId,
+                amount: U128,
+                locker_id: AccountId,
+                expires_at: Option<near_sdk::json_types::U64>,
+            ) {
+                self.$token.mt_lock(token_id, amount, locker_id, expires_at)
+            }
+
+            #[payable]
+            fn mt_unlock(
+                &mut self,
+                token_id: $crate::TokenId,
+                owner_id: AccountId,
+                amount: Option<U128>,
+            ) {
+                self.$token.mt_unlock(token_id, owner_id, amount)
+            }
+
+            #[payable]
+            fn mt_seize(
+                &mut self,
+                token_id: $crate::TokenId,
+                owner_id: AccountId,
+                amount: U128,
+                receiver_id: Option<AccountId>,
+                memo: Option<String>,
+            ) {
+                let sender_id = env::predecessor_account_id();
+                let receiver = receiver_id.clone().unwrap_or_else(|| sender_id.clone());
+                let owner_ids = vec![owner_id.clone()];
+                let (token_ids, amounts) = (vec![token_id.clone()], vec![amount]);
+                $crate::hooks::MultiTokenHooks::before_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver,
+                    &token_ids,
+                    &amounts,
+                );
+                self.$token.mt_seize(token_id, owner_id, amount, receiver_id, memo);
+                $crate::hooks::MultiTokenHooks::after_transfer(
+                    self,
+                    &sender_id,
+                    &owner_ids,
+                    &receiver,
+                    &token_ids,
+                    &amounts,
+                );
+            }
+
+            fn mt_locked_balance_of(
+                &self,
+                account_id: AccountId,
+                token_id: $crate::TokenId,
+            ) -> U128 {
+                self.$token.mt_locked_balance_of(account_id, token_id)
+            }
+
+            fn mt_locks(&self, account_id: AccountId, token_id: $crate::TokenId) -> Vec<Lock> {
+                self.$token.mt_locks(account_id, token_id)
+            }
+        }
+    };
+}
+
+/// Exposes permit keys and the transfers signed with them, which any relayer can submit.
+#[macro_export]
+macro_rules! impl_multi_token_permit {
+    ($contract: ident, $token: ident) => {
+        use $crate::permit::{MultiTokenPermit, Permit};
+
+        #[near_bindgen]
+        impl MultiTokenPermit for $contract {
+            #[payable]
+            fn mt_set_permit_key(&mut self, public_key: Option<near_sdk::PublicKey>) {
+                self.$token.mt_set_permit_key(public_key)
+            }
+
+            #[payable]
+            fn mt_transfer_with_permit(
+                &mut self,
+                permit: Permit,
+                signature: near_sdk::json_types::Base64VecU8,
+                memo: Option<String>,
+            ) {
+                // The owner signed the transfer, so it is the sender as far as the hooks go
+                let owner_ids = vec![permit.owner_id.clone()];
+                let (receiver_id, token_ids, amounts) =
+                    (permit.receiver_id.clone(), vec![permit.token_id.clone()], vec![permit.amount]);
+                $crate::hooks::MultiTokenHooks::before_transfer(
+                    self,
+                    &owner_ids[0],
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+                self.$token.mt_transfer_with_permit(permit, signature, memo);
+                $crate::hooks::MultiTokenHooks::after_transfer(
+                    self,
+                    &owner_ids[0],
+                    &owner_ids,
+                    &receiver_id,
+                    &token_ids,
+                    &amounts,
+                );
+            }
+
+            fn mt_permit_key(&self, account_id: AccountId) -> Option<near_sdk::PublicKey> {
+                self.$token.mt_permit_key(account_id)
+            }
+
+            fn mt_permit_nonce(&self, account_id: AccountId) -> near_sdk::json_types::U64 {
+                self.$token.mt_permit_nonce(account_id)
             }
         }
     };
@@ -0,0 +1,156 @@
+use crate::events::MtMetadataUpdate;
+use crate::metadata::{
+	BaseTokenMetadata, MultiTokenMetadataUpdate, TokenMetadata, TokenMetadataAll,
+	TokenMetadataPatch,
+};
+use crate::token::TokenId;
+use crate::utils::{assert_at_least_one_yocto, block_timestamp_iso8601, refund_deposit};
+use crate::MultiToken;
+use near_sdk::{assert_one_yocto, env, require};
+
+impl MultiToken {
+	/// Stores `base_metadata` under its id. Base metadata that is already stored is shared by
+	/// every token referencing it, so it can only be given again with identical content.
+	pub fn internal_set_base_metadata(&mut self, base_metadata: &BaseTokenMetadata) {
+		let base_metadata_by_id = self
+			.base_metadata_by_id
+			.as_mut()
+			.unwrap_or_else(|| env::panic_str("MultiToken does not support Metadata"));
+		match base_metadata_by_id.get(&base_metadata.id) {
+			Some(stored) => require!(
+				&stored == base_metadata,
+				"Base metadata with the same id but different content already exists"
+			),
+			None => {
+				base_metadata_by_id.insert(&base_metadata.id, base_metadata);
+			}
+		}
+	}
+
+	/// Stores the token level metadata of `token_id` along with a reference to its base metadata
+	pub fn internal_set_token_metadata(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		metadata: &TokenMetadataAll,
+	) {
+		self.assert_metadata_not_frozen(token_id);
+		self.internal_set_base_metadata(&metadata.base);
+		self.token_metadata_by_id.as_mut().unwrap().insert(token_id, &metadata.token);
+		self.base_metadata_id_by_token_id.as_mut().unwrap().insert(token_id, &metadata.base.id);
+	}
+
+	fn assert_metadata_not_frozen(&self, #[allow(clippy::ptr_arg)] token_id: &TokenId) {
+		if let Some(frozen_metadata) = &self.frozen_metadata {
+			require!(!frozen_metadata.contains(token_id), "The metadata of the token is frozen");
+		}
+	}
+
+	/// Panics unless the predecessor created `token_id` or owns the contract
+	fn assert_metadata_updater(&self, #[allow(clippy::ptr_arg)] token_id: &TokenId) {
+		let updater = env::predecessor_account_id();
+		let creator = self
+			.metadata_creator_by_id
+			.as_ref()
+			.unwrap_or_else(|| env::panic_str("MultiToken does not support Metadata"))
+			.get(token_id);
+		require!(
+			creator.as_ref() == Some(&updater) || updater == self.owner_id,
+			"Only the creator of the token or the contract owner can update its metadata"
+		);
+		self.assert_metadata_not_frozen(token_id);
+	}
+
+	pub fn internal_get_token_metadata(
+		&self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+	) -> Option<TokenMetadata> {
+		self.token_metadata_by_id.as_ref()?.get(token_id)
+	}
+
+	pub fn internal_get_base_metadata(
+		&self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+	) -> Option<BaseTokenMetadata> {
+		let base_metadata_id = self.base_metadata_id_by_token_id.as_ref()?.get(token_id)?;
+		self.base_metadata_by_id.as_ref()?.get(&base_metadata_id)
+	}
+
+	pub fn internal_get_token_metadata_all(
+		&self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+	) -> Option<TokenMetadataAll> {
+		Some(TokenMetadataAll {
+			base: self.internal_get_base_metadata(token_id)?,
+			token: self.internal_get_token_metadata(token_id)?,
+		})
+	}
+
+	pub fn mt_metadata_token_all(&self, token_ids: Vec<TokenId>) -> Vec<Option<TokenMetadataAll>> {
+		token_ids.iter().map(|token_id| self.internal_get_token_metadata_all(token_id)).collect()
+	}
+
+	pub fn mt_metadata_token_by_token_id(
+		&self,
+		token_ids: Vec<TokenId>,
+	) -> Vec<Option<TokenMetadata>> {
+		token_ids.iter().map(|token_id| self.internal_get_token_metadata(token_id)).collect()
+	}
+
+	pub fn mt_metadata_base_by_token_id(
+		&self,
+		token_ids: Vec<TokenId>,
+	) -> Vec<Option<BaseTokenMetadata>> {
+		token_ids.iter().map(|token_id| self.internal_get_base_metadata(token_id)).collect()
+	}
+
+	pub fn mt_metadata_base_by_metadata_id(
+		&self,
+		base_metadata_ids: Vec<String>,
+	) -> Vec<Option<BaseTokenMetadata>> {
+		let base_metadata_by_id = match self.base_metadata_by_id.as_ref() {
+			Some(base_metadata_by_id) => base_metadata_by_id,
+			None => return vec![None; base_metadata_ids.len()],
+		};
+		base_metadata_ids
+			.iter()
+			.map(|base_metadata_id| base_metadata_by_id.get(base_metadata_id))
+			.collect()
+	}
+}
+
+impl MultiTokenMetadataUpdate for MultiToken {
+	fn mt_update_metadata(
+		&mut self,
+		token_id: TokenId,
+		patch: TokenMetadataPatch,
+		memo: Option<String>,
+	) {
+		assert_at_least_one_yocto();
+		self.assert_metadata_updater(&token_id);
+		let initial_storage_usage = env::storage_usage();
+
+		let mut metadata = self
+			.internal_get_token_metadata(&token_id)
+			.unwrap_or_else(|| env::panic_str("Cannot find the metadata for the given token"));
+		metadata.apply(patch);
+		metadata.updated_at = Some(block_timestamp_iso8601());
+		metadata.assert_valid();
+		self.token_metadata_by_id.as_mut().unwrap().insert(&token_id, &metadata);
+
+		refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+		MtMetadataUpdate { token_ids: &[token_id], memo: memo.as_deref() }.emit();
+	}
+
+	fn mt_freeze_metadata(&mut self, token_id: TokenId) {
+		assert_one_yocto();
+		self.assert_metadata_updater(&token_id);
+		self.frozen_metadata.as_mut().unwrap().insert(&token_id);
+	}
+
+	fn mt_is_metadata_frozen(&self, token_id: TokenId) -> bool {
+		self.frozen_metadata
+			.as_ref()
+			.map(|frozen_metadata| frozen_metadata.contains(&token_id))
+			.unwrap_or(false)
+	}
+}
@@ -0,0 +1,233 @@
+mod metadata_impl;
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::require;
+use near_sdk::serde::{Deserialize, Deserializer, Serialize};
+use crate::token::TokenId;
+
+/// This spec can be treated like a version of the standard.
+pub const MT_METADATA_SPEC: &str = "mt-1.0.0";
+/// Maximum length in bytes of the `icon` data URL
+pub const MAX_ICON_LENGTH: usize = 4_096;
+/// Maximum length in bytes of the `extra` field
+pub const MAX_EXTRA_LENGTH: usize = 4_096;
+/// Maximum number of decimals, matching the precision of NEAR itself
+pub const MAX_DECIMALS: u8 = 24;
+
+/// Metadata for the multi token contract itself, shared by every token id.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtContractMetadata {
+    pub spec: String, // required, essentially a version like "mt-1.0.0"
+    pub name: String, // required, ex. "Mosaics"
+}
+
+/// Metadata shared by a series of tokens. It is stored once under its `id` and referenced by
+/// every token minted with it, so large fields like `icon` are not duplicated per token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BaseTokenMetadata {
+    pub id: String,                 // required, unique id of this base metadata
+    pub name: String,               // required, ex. "Mosaics"
+    pub symbol: Option<String>,     // ex. "MOSIAC"
+    pub icon: Option<String>,       // Data URL
+    pub decimals: Option<u8>,       // precision decimals for tokens that need this information
+    pub base_uri: Option<String>, // Centralized gateway known to have reliable access to decentralized storage assets referenced by `reference` or `media` URLs
+    pub reference: Option<String>, // URL to a JSON file with more info
+    pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
+    pub copies: Option<u64>, // number of copies of this set of metadata in existence when token was minted.
+}
+
+/// Metadata specific to a single token id.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenMetadata {
+    pub title: Option<String>, // ex. "Arch Nemesis: Mail Carrier" or "Parcel #5055"
+    pub description: Option<String>, // free-form description
+    pub media: Option<String>, // URL to associated media, preferably to decentralized, content-addressed storage
+    pub media_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of content referenced by the `media` field. Required if `media` is included.
+    pub issued_at: Option<String>, // ISO 8601 datetime when token was issued or minted
+    pub expires_at: Option<String>, // ISO 8601 datetime when token expires
+    pub starts_at: Option<String>, // ISO 8601 datetime when token starts being valid
+    pub updated_at: Option<String>, // ISO 8601 datetime when token was last updated
+    pub extra: Option<String>, // anything extra the token wants to store on-chain. Can be stringified JSON.
+    pub reference: Option<String>, // URL to an off-chain JSON file with more info.
+    pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
+}
+
+/// Changes to the token level metadata of a token id. Fields left out of the patch are kept as
+/// they are, while a field set to `null` is cleared.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(crate = "near_sdk::serde", default)]
+pub struct TokenMetadataPatch {
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub title: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub description: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub media: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub media_hash: Option<Option<Base64VecU8>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub issued_at: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub expires_at: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub starts_at: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub extra: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub reference: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_patch_field")]
+    pub reference_hash: Option<Option<Base64VecU8>>,
+}
+
+/// The base and token metadata of a token id, as returned by the views and given when minting.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenMetadataAll {
+    pub base: BaseTokenMetadata,
+    pub token: TokenMetadata,
+}
+
+impl MtContractMetadata {
+    pub fn assert_valid(&self) {
+        require!(self.spec == MT_METADATA_SPEC, "Spec is not MT metadata");
+    }
+}
+
+impl BaseTokenMetadata {
+    pub fn assert_valid(&self) {
+        assert_valid_reference(&self.reference, &self.reference_hash);
+        if let Some(icon) = &self.icon {
+            require!(icon.len() <= MAX_ICON_LENGTH, "Icon exceeds the maximum length");
+        }
+        if let Some(decimals) = self.decimals {
+            require!(decimals <= MAX_DECIMALS, "Decimals exceed the maximum");
+        }
+    }
+}
+
+impl TokenMetadata {
+    pub fn assert_valid(&self) {
+        require!(
+            self.media.is_some() == self.media_hash.is_some(),
+            "Media and media hash must be set together"
+        );
+        if let Some(media_hash) = &self.media_hash {
+            require!(media_hash.0.len() == 32, "Media hash has to be 32 bytes");
+        }
+        assert_valid_reference(&self.reference, &self.reference_hash);
+        if let Some(extra) = &self.extra {
+            require!(extra.len() <= MAX_EXTRA_LENGTH, "Extra exceeds the maximum length");
+        }
+    }
+
+    /// Overwrites the fields that are set in `patch`, clearing the ones set to `None`
+    fn apply(&mut self, patch: TokenMetadataPatch) {
+        if let Some(title) = patch.title {
+            self.title = title;
+        }
+        if let Some(description) = patch.description {
+            self.description = description;
+        }
+        if let Some(media) = patch.media {
+            self.media = media;
+        }
+        if let Some(media_hash) = patch.media_hash {
+            self.media_hash = media_hash;
+        }
+        if let Some(issued_at) = patch.issued_at {
+            self.issued_at = issued_at;
+        }
+        if let Some(expires_at) = patch.expires_at {
+            self.expires_at = expires_at;
+        }
+        if let Some(starts_at) = patch.starts_at {
+            self.starts_at = starts_at;
+        }
+        if let Some(extra) = patch.extra {
+            self.extra = extra;
+        }
+        if let Some(reference) = patch.reference {
+            self.reference = reference;
+        }
+        if let Some(reference_hash) = patch.reference_hash {
+            self.reference_hash = reference_hash;
+        }
+    }
+}
+
+impl TokenMetadataAll {
+    pub fn assert_valid(&self) {
+        self.base.assert_valid();
+        self.token.assert_valid();
+    }
+}
+
+/// Reads a field which is present in the patch, even as `null`, as `Some`
+fn deserialize_patch_field<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+fn assert_valid_reference(reference: &Option<String>, reference_hash: &Option<Base64VecU8>) {
+    require!(
+        reference.is_some() == reference_hash.is_some(),
+        "Reference and reference hash must be set together"
+    );
+    if let Some(reference_hash) = reference_hash {
+        require!(reference_hash.0.len() == 32, "Reference hash has to be 32 bytes");
+    }
+}
+
+/// Offers details on the contract and token metadata. All token views return the results in a
+/// 1-1 mapping with the given ids, with None for the ids that have no metadata.
+pub trait MultiTokenMetadataProvider {
+    fn mt_metadata_contract(&self) -> MtContractMetadata;
+
+    fn mt_metadata_token_all(&self, token_ids: Vec<TokenId>) -> Vec<Option<TokenMetadataAll>>;
+
+    fn mt_metadata_token_by_token_id(&self, token_ids: Vec<TokenId>) -> Vec<Option<TokenMetadata>>;
+
+    fn mt_metadata_base_by_token_id(
+        &self,
+        token_ids: Vec<TokenId>,
+    ) -> Vec<Option<BaseTokenMetadata>>;
+
+    fn mt_metadata_base_by_metadata_id(
+        &self,
+        base_metadata_ids: Vec<String>,
+    ) -> Vec<Option<BaseTokenMetadata>>;
+}
+
+/// Lets the creator of a token, or the owner of the contract, fix its token level metadata after
+/// minting. The base metadata is shared between tokens and cannot be updated.
+pub trait MultiTokenMetadataUpdate {
+    /// Applies `patch` to the token level metadata of `token_id` and sets `updated_at` to the
+    /// current block time.
+    ///
+    /// Requirements
+    /// * Caller of the method must attach a deposit of at least 1 yoctoⓃ for security purposes,
+    ///   and enough to cover the storage if the metadata grows
+    /// * Caller must be the account that created the token or the owner of the contract
+    /// * The metadata of the token must not be frozen and must still be valid once patched
+    fn mt_update_metadata(
+        &mut self,
+        token_id: TokenId,
+        patch: TokenMetadataPatch,
+        memo: Option<String>,
+    );
+
+    /// Permanently prevents any further update to the metadata of `token_id`. Has the same caller
+    /// requirements as `mt_update_metadata`, with a deposit of exactly 1 yoctoⓃ.
+    fn mt_freeze_metadata(&mut self, token_id: TokenId);
+
+    fn mt_is_metadata_frozen(&self, token_id: TokenId) -> bool;
+}
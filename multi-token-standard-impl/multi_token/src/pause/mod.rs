@@ -0,0 +1,45 @@
+mod pause_impl;
+
+pub use self::pause_impl::*;
+
+use crate::token::TokenId;
+use near_sdk::json_types::U128;
+
+/// Lets the owner of the contract stop token movements, either for specific token ids, ex. while
+/// a buyout is settling or a vault is under dispute, or for every token in an emergency.
+///
+/// While a token is frozen, or while the contract is paused, its transfers and burns panic and
+/// `mt_resolve_transfer` leaves the tokens with the receiver instead of refunding them.
+pub trait MultiTokenPause {
+    /// Stops all transfers and burns of `token_ids`. Only callable by the owner of the contract,
+    /// which must attach enough deposit to cover the storage used.
+    fn mt_freeze_transfers(&mut self, token_ids: Vec<TokenId>);
+
+    /// Allows `token_ids` to be transferred again. Only callable by the owner of the contract
+    /// with 1 yoctoⓃ attached, which gets back the storage paid for freezing them.
+    fn mt_unfreeze_transfers(&mut self, token_ids: Vec<TokenId>);
+
+    /// Stops every transfer and burn. Only callable by the owner of the contract with 1 yoctoⓃ
+    /// attached.
+    fn mt_pause(&mut self);
+
+    /// Lifts [`mt_pause`](MultiTokenPause::mt_pause). Only callable by the owner of the contract
+    /// with 1 yoctoⓃ attached.
+    fn mt_unpause(&mut self);
+
+    fn mt_is_paused(&self) -> bool;
+
+    fn mt_is_transfer_frozen(&self, token_id: TokenId) -> bool;
+
+    /// Get a list of the frozen token ids
+    ///
+    /// Arguments:
+    /// * `from_index`: a string representing an unsigned 128-bit integer,
+    ///    representing the starting index of token ids to return
+    /// * `limit`: the maximum number of token ids to return
+    fn mt_transfer_frozen_token_ids(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<TokenId>;
+}
@@ -0,0 +1,88 @@
+use crate::pause::MultiTokenPause;
+use crate::token::TokenId;
+use crate::utils::{assert_at_least_one_yocto, refund_deposit};
+use crate::MultiToken;
+use near_sdk::json_types::U128;
+use near_sdk::{assert_one_yocto, env, require};
+
+impl MultiToken {
+	/// Whether `token_id` can currently be moved, i.e. the contract is not paused and the token
+	/// is not frozen
+	pub fn internal_is_transferable(&self, #[allow(clippy::ptr_arg)] token_id: &TokenId) -> bool {
+		!self.paused && !self.transfer_frozen_token_ids.contains(token_id)
+	}
+
+	/// Panics if the contract is paused or `token_id` is frozen
+	pub fn assert_transferable(&self, #[allow(clippy::ptr_arg)] token_id: &TokenId) {
+		require!(!self.paused, "Multi token transfers are paused");
+		if self.transfer_frozen_token_ids.contains(token_id) {
+			env::panic_str(format!("Transfers of {} are frozen", token_id).as_str());
+		}
+	}
+
+	fn assert_owner(&self) {
+		require!(
+			env::predecessor_account_id() == self.owner_id,
+			"Only the owner can pause or freeze transfers"
+		);
+	}
+}
+
+impl MultiTokenPause for MultiToken {
+	fn mt_freeze_transfers(&mut self, token_ids: Vec<TokenId>) {
+		assert_at_least_one_yocto();
+		self.assert_owner();
+		let initial_storage_usage = env::storage_usage();
+		for token_id in token_ids.iter() {
+			require!(self.token_type_index.contains_key(token_id), "Token not found");
+			self.transfer_frozen_token_ids.insert(token_id);
+		}
+		refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+	}
+
+	fn mt_unfreeze_transfers(&mut self, token_ids: Vec<TokenId>) {
+		assert_one_yocto();
+		self.assert_owner();
+		let initial_storage_usage = env::storage_usage();
+		for token_id in token_ids.iter() {
+			self.transfer_frozen_token_ids.remove(token_id);
+		}
+		// Only the owner freezes transfers, so it paid for the storage released here
+		Self::refund_released_storage(env::predecessor_account_id(), initial_storage_usage);
+	}
+
+	fn mt_pause(&mut self) {
+		assert_one_yocto();
+		self.assert_owner();
+		self.paused = true;
+	}
+
+	fn mt_unpause(&mut self) {
+		assert_one_yocto();
+		self.assert_owner();
+		self.paused = false;
+	}
+
+	fn mt_is_paused(&self) -> bool {
+		self.paused
+	}
+
+	fn mt_is_transfer_frozen(&self, token_id: TokenId) -> bool {
+		self.transfer_frozen_token_ids.contains(&token_id)
+	}
+
+	fn mt_transfer_frozen_token_ids(
+		&self,
+		from_index: Option<U128>,
+		limit: Option<u64>,
+	) -> Vec<TokenId> {
+		let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+		require!(
+			self.transfer_frozen_token_ids.len() as u128 >= start_index,
+			"Out of bounds, please use a smaller from_index."
+		);
+		let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+		require!(limit != 0, "Cannot provide limit of 0.");
+		self.transfer_frozen_token_ids.iter().skip(start_index as usize).take(limit).collect()
+	}
+}
@@ -0,0 +1,52 @@
+mod payout_impl;
+
+pub use self::payout_impl::*;
+
+use crate::token::TokenId;
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+use std::collections::HashMap;
+
+/// Royalties are given in basis points of the sale price, ex. 250 for 2.5%
+pub const ROYALTY_DENOMINATOR: u32 = 10_000;
+/// The most accounts a token can pay royalties to
+pub const MAX_ROYALTY_ACCOUNTS: usize = 10;
+
+/// How a sale price is split between accounts, following
+/// [NEP-199](https://nomicon.io/Standards/NonFungibleToken/Payout)
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Payout {
+    pub payout: HashMap<AccountId, U128>,
+}
+
+/// Lets marketplaces honor the royalties set when a token is minted on secondary sales.
+pub trait MultiTokenPayout {
+    /// How `balance`, the price paid for `amount` of `token_id`, should be split. The owner of an
+    /// NFT type token receives what is left after royalties. The holder selling FT type tokens
+    /// is not known to this view, so only the royalties are returned and the rest of `balance`
+    /// goes to the seller.
+    /// * `max_len_payout`: panics if the payout would go to more accounts than this
+    fn mt_payout(
+        &self,
+        token_id: TokenId,
+        amount: U128,
+        balance: U128,
+        max_len_payout: Option<u32>,
+    ) -> Payout;
+
+    /// Same as [`mt_transfer`](crate::core::MultiTokenCore::mt_transfer), returning how `balance`
+    /// should be split, including the share of the owner the tokens were transferred from.
+    #[allow(clippy::too_many_arguments)]
+    fn mt_transfer_payout(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        amount: U128,
+        approval: Option<(AccountId, u64)>,
+        memo: Option<String>,
+        balance: U128,
+        max_len_payout: Option<u32>,
+    ) -> Payout;
+}
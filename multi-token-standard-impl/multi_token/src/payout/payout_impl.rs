@@ -0,0 +1,116 @@
+use crate::core::MultiTokenCore;
+use crate::payout::{MultiTokenPayout, Payout, MAX_ROYALTY_ACCOUNTS, ROYALTY_DENOMINATOR};
+use crate::token::{TokenId, TokenType};
+use crate::MultiToken;
+use near_sdk::json_types::U128;
+use near_sdk::{env, require, AccountId, Balance};
+use std::collections::HashMap;
+
+impl MultiToken {
+	/// Sets the royalties of `token_id`, in basis points of the sale price. Meant to be called
+	/// when the token is minted, the royalties of a token cannot be changed once set.
+	pub fn internal_set_royalty(
+		&mut self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		royalty: HashMap<AccountId, u32>,
+	) {
+		require!(self.token_type_index.contains_key(token_id), "Token not found");
+		require!(
+			royalty.len() <= MAX_ROYALTY_ACCOUNTS,
+			format!("Royalties can be paid to at most {} accounts", MAX_ROYALTY_ACCOUNTS)
+		);
+		let total: u64 = royalty.values().map(|bps| u64::from(*bps)).sum();
+		require!(
+			total <= u64::from(ROYALTY_DENOMINATOR),
+			"Royalties cannot add up to more than 100%"
+		);
+		let royalties_by_id = self
+			.royalties_by_id
+			.as_mut()
+			.unwrap_or_else(|| env::panic_str("MultiToken does not support Payouts"));
+		if royalties_by_id.insert(token_id, &royalty).is_some() {
+			env::panic_str(format!("The royalties of {} are already set", token_id).as_str());
+		}
+	}
+
+	/// Splits `balance` between the royalties of `token_id` and `owner_id`, who gets the rest
+	pub fn internal_payout(
+		&self,
+		#[allow(clippy::ptr_arg)] token_id: &TokenId,
+		owner_id: Option<&AccountId>,
+		balance: Balance,
+		max_len_payout: Option<u32>,
+	) -> Payout {
+		let royalty = self
+			.royalties_by_id
+			.as_ref()
+			.unwrap_or_else(|| env::panic_str("MultiToken does not support Payouts"))
+			.get(token_id)
+			.unwrap_or_default();
+		let mut payout: HashMap<AccountId, U128> = HashMap::new();
+		let mut paid: Balance = 0;
+		for (account_id, bps) in royalty {
+			let share = royalty_to_payout(bps, balance);
+			paid += share;
+			payout.insert(account_id, share.into());
+		}
+		if let Some(owner_id) = owner_id {
+			let owner_share =
+				payout.get(owner_id).map(|share| share.0).unwrap_or(0) + balance - paid;
+			payout.insert(owner_id.clone(), owner_share.into());
+		}
+		if let Some(max_len_payout) = max_len_payout {
+			require!(
+				payout.len() <= max_len_payout as usize,
+				format!("The payout goes to {} accounts, more than max_len_payout", payout.len())
+			);
+		}
+		Payout { payout }
+	}
+}
+
+/// `bps` basis points of `balance`, split up so that large balances cannot overflow
+fn royalty_to_payout(bps: u32, balance: Balance) -> Balance {
+	let denominator = Balance::from(ROYALTY_DENOMINATOR);
+	balance / denominator * Balance::from(bps)
+		+ balance % denominator * Balance::from(bps) / denominator
+}
+
+impl MultiTokenPayout for MultiToken {
+	fn mt_payout(
+		&self,
+		token_id: TokenId,
+		amount: U128,
+		balance: U128,
+		max_len_payout: Option<u32>,
+	) -> Payout {
+		let owner_id = match self.token_type_index.get(&token_id).expect("Token not found") {
+			TokenType::Nft => {
+				require!(amount.0 == 1, "The amount of an NFT type token must be 1");
+				self.nft_owner_by_id.get(&token_id)
+			}
+			TokenType::Ft => {
+				require!(amount.0 > 0, "The amount should be a positive number");
+				None
+			}
+		};
+		self.internal_payout(&token_id, owner_id.as_ref(), balance.0, max_len_payout)
+	}
+
+	fn mt_transfer_payout(
+		&mut self,
+		receiver_id: AccountId,
+		token_id: TokenId,
+		amount: U128,
+		approval: Option<(AccountId, u64)>,
+		memo: Option<String>,
+		balance: U128,
+		max_len_payout: Option<u32>,
+	) -> Payout {
+		let owner_id =
+			self.internal_token_owner(&token_id, &env::predecessor_account_id(), approval.as_ref());
+		let payout = self.internal_payout(&token_id, Some(&owner_id), balance.0, max_len_payout);
+		self.mt_transfer(receiver_id, token_id, amount, approval, memo);
+		payout
+	}
+}
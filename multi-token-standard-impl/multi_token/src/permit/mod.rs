@@ -0,0 +1,49 @@
+mod permit_impl;
+
+pub use self::permit_impl::*;
+
+use crate::token::TokenId;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{AccountId, PublicKey};
+
+/// A transfer signed off-chain by `owner_id`. The owner signs the Borsh serialization of
+/// `(contract_id, permit)` with its permit key, where `contract_id` is the account of the multi
+/// token contract, so that a permit cannot be replayed on another contract.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Permit {
+    pub owner_id: AccountId,
+    pub receiver_id: AccountId,
+    pub token_id: TokenId,
+    pub amount: U128,
+    /// Must equal the current permit nonce of the owner, which every used permit increments
+    pub nonce: U64,
+    /// Block timestamp in nanoseconds after which the permit can no longer be used
+    pub deadline: U64,
+}
+
+/// Gasless transfers: holders sign transfers with an ed25519 key registered on the contract, and
+/// any relayer submits them, paying for the gas.
+pub trait MultiTokenPermit {
+    /// Registers the ed25519 `public_key` the caller signs permits with, or removes it when not
+    /// given. The caller must attach enough deposit to cover the storage used.
+    fn mt_set_permit_key(&mut self, public_key: Option<PublicKey>);
+
+    /// Executes the transfer `permit`, signed by its owner. Callable by anyone. The attached
+    /// deposit pays for recording the nonce of the owner on its first permit, and for registering
+    /// the receiver when receivers are registered on transfer. The excess deposit is refunded.
+    /// * `signature`: the ed25519 signature of the permit
+    fn mt_transfer_with_permit(
+        &mut self,
+        permit: Permit,
+        signature: Base64VecU8,
+        memo: Option<String>,
+    );
+
+    fn mt_permit_key(&self, account_id: AccountId) -> Option<PublicKey>;
+
+    /// The nonce the next permit of `account_id` must use
+    fn mt_permit_nonce(&self, account_id: AccountId) -> U64;
+}
@@ -0,0 +1,102 @@
+use crate::permit::{MultiTokenPermit, Permit};
+use crate::utils::{assert_at_least_one_yocto, refund_deposit};
+use crate::MultiToken;
+use ed25519_dalek::Verifier;
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::json_types::{Base64VecU8, U64};
+use near_sdk::{assert_one_yocto, env, require, AccountId, Balance, CurveType, Promise, PublicKey};
+use std::convert::TryFrom;
+
+impl MultiToken {
+	/// Checks that `permit` can be used and was signed by its owner, then consumes its nonce
+	pub fn internal_use_permit(&mut self, permit: &Permit, signature: &[u8]) {
+		require!(env::block_timestamp() <= permit.deadline.0, "The permit has expired");
+		let nonce = self.permit_nonces.get(&permit.owner_id).unwrap_or(0);
+		require!(permit.nonce.0 == nonce, format!("Expected the permit nonce to be {}", nonce));
+		let public_key = self.permit_keys.get(&permit.owner_id).unwrap_or_else(|| {
+			env::panic_str(format!("{} has no permit key", permit.owner_id).as_str())
+		});
+		let message = (env::current_account_id(), permit).try_to_vec().unwrap();
+		require!(verify_ed25519(&public_key, &message, signature), "Invalid permit signature");
+		self.permit_nonces.insert(&permit.owner_id, &(nonce + 1));
+	}
+}
+
+fn verify_ed25519(public_key: &PublicKey, message: &[u8], signature: &[u8]) -> bool {
+	// The first byte of a public key is its curve type
+	let public_key = match ed25519_dalek::PublicKey::from_bytes(&public_key.as_bytes()[1..]) {
+		Ok(public_key) => public_key,
+		Err(_) => return false,
+	};
+	match ed25519_dalek::Signature::try_from(signature) {
+		Ok(signature) => public_key.verify(message, &signature).is_ok(),
+		Err(_) => false,
+	}
+}
+
+impl MultiTokenPermit for MultiToken {
+	fn mt_set_permit_key(&mut self, public_key: Option<PublicKey>) {
+		let account_id = env::predecessor_account_id();
+		match public_key {
+			Some(public_key) => {
+				assert_at_least_one_yocto();
+				require!(
+					public_key.curve_type() == CurveType::ED25519,
+					"Permit keys must be ed25519 keys"
+				);
+				let initial_storage_usage = env::storage_usage();
+				self.permit_keys.insert(&account_id, &public_key);
+				refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+			}
+			None => {
+				assert_one_yocto();
+				let initial_storage_usage = env::storage_usage();
+				self.permit_keys.remove(&account_id);
+				Self::refund_released_storage(account_id, initial_storage_usage);
+			}
+		}
+	}
+
+	fn mt_transfer_with_permit(
+		&mut self,
+		permit: Permit,
+		signature: Base64VecU8,
+		memo: Option<String>,
+	) {
+		let initial_storage_usage = env::storage_usage();
+		self.internal_use_permit(&permit, &signature.0);
+		// The first permit of an owner records its nonce, which the relayer pays for
+		let nonce_cost =
+			Balance::from(env::storage_usage() - initial_storage_usage) * env::storage_byte_cost();
+		require!(
+			env::attached_deposit() >= nonce_cost,
+			format!("Must attach {} yoctoNEAR to cover storage", nonce_cost)
+		);
+		let deposit = env::attached_deposit() - nonce_cost;
+		if self.auto_register_receivers {
+			self.internal_storage_register_receiver(
+				&[permit.token_id.clone()],
+				&permit.receiver_id,
+				deposit,
+			);
+		} else if deposit > 1 {
+			Promise::new(env::predecessor_account_id()).transfer(deposit);
+		}
+		self.internal_transfer(
+			&permit.owner_id,
+			&permit.receiver_id,
+			&permit.token_id,
+			permit.amount.0,
+			None,
+			memo,
+		);
+	}
+
+	fn mt_permit_key(&self, account_id: AccountId) -> Option<PublicKey> {
+		self.permit_keys.get(&account_id)
+	}
+
+	fn mt_permit_nonce(&self, account_id: AccountId) -> U64 {
+		self.permit_nonces.get(&account_id).unwrap_or(0).into()
+	}
+}
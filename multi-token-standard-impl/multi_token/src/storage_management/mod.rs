@@ -1,16 +1,20 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::json_types::U128;
-use near_sdk::serde::Serialize;
+use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::AccountId;
 use crate::TokenId;
 
-#[derive(Serialize)]
+/// Storage balance of an account, pooled across every token id it is registered for.
+/// `total` is everything deposited and not yet withdrawn, `available` is the part of it not
+/// locked by registrations.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct StorageBalance {
     pub total: U128,
     pub available: U128,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct StorageBalanceBounds {
     pub min: U128,
@@ -18,6 +22,9 @@ pub struct StorageBalanceBounds {
 }
 
 pub trait StorageManagement {
+    /// Deposits the attached Ⓝ into the storage balance of `account_id`, or the predecessor
+    /// account, then registers it for each of `token_ids` it is not yet registered for.
+    /// Every registration draws `storage_balance_bounds` from the available balance.
     // if `registration_only=true` MUST refund above the minimum balance if the account didn't exist and
     //     refund full deposit if the account exists.
     fn storage_deposit(
@@ -41,9 +48,10 @@ pub trait StorageManagement {
     /// function-call access-key call (UX wallet security)
     ///
     /// Returns the StorageBalance structure showing updated balances.
-    fn storage_withdraw(&mut self, token_ids:Vec<TokenId>, amount: Option<U128>) -> StorageBalance;
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance;
 
-    /// Unregisters the predecessor account and returns the storage NEAR deposit back.
+    /// Unregisters the predecessor account from `token_ids` and releases the storage it locked
+    /// back into its available balance.
     ///
     /// If the predecessor account is not registered, the function MUST return `false` without panic.
     ///
@@ -56,6 +64,8 @@ pub trait StorageManagement {
     /// Returns `false` iff account was not registered before.
     fn storage_unregister(&mut self, token_ids:Vec<TokenId>, force: Option<bool>) -> Vec<bool>;
 
+    /// Returns the deposit required to register for `token_ids`, leaving out the ones
+    /// `account_id` is already registered for
     fn storage_balance_bounds(&self, token_id:Vec<TokenId>, account_id: Option<AccountId>) -> StorageBalanceBounds;
-    fn storage_balance_of(&self, token_ids:Vec<TokenId>, account_id: AccountId) -> Option<StorageBalance>;
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance>;
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
-use crate::metadata::MultiTokenMetadata;
+use crate::metadata::TokenMetadataAll;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::AccountId;
 use std::collections::HashMap;
@@ -15,13 +16,25 @@ pub enum TokenType {
 }
 
 /// In this implementation, the Token struct takes two extensions standards (metadata and approval) as optional fields, as they are frequently used in modern NFTs.
+/// FT type tokens can have many holders, so `owner_id` is only set for them when the token is
+/// viewed on behalf of a specific holder.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Token {
     pub token_id: TokenId,
     pub token_type: TokenType, 
-    pub owner_id: AccountId,
-    pub supply: u128,
-    pub metadata: Option<MultiTokenMetadata>,
+    pub owner_id: Option<AccountId>,
+    pub supply: U128,
+    pub metadata: Option<TokenMetadataAll>,
     pub approved_account_ids: Option<HashMap<AccountId, u64>>,
+}
+
+/// Supply accounting of a token. `minted` only ever grows and `burned` counts every token taken
+/// out of circulation, whether burned by its holder or dropped as an account was closed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenSupply {
+    pub minted: U128,
+    pub burned: U128,
+    pub circulating: U128,
 }
\ No newline at end of file
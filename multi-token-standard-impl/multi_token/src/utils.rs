@@ -1,4 +1,4 @@
-use near_sdk::{env, Balance, Promise};
+use near_sdk::{env, require, Balance, Promise};
 pub fn refund_deposit(storage_used: u64) {
 	let required_cost = env::storage_byte_cost() * Balance::from(storage_used);
 	let attached_deposit = env::attached_deposit();
@@ -12,3 +12,32 @@ pub fn refund_deposit(storage_used: u64) {
 		Promise::new(env::predecessor_account_id()).transfer(refund);
 	}
 }
+
+pub fn assert_at_least_one_yocto() {
+	require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR")
+}
+
+/// Formats the current block timestamp as an ISO 8601 UTC datetime, ex. "2022-03-01T12:00:00Z"
+pub fn block_timestamp_iso8601() -> String {
+	let secs = env::block_timestamp() / 1_000_000_000;
+	let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+	// Converts days since the unix epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+	let z = days + 719_468;
+	let era = z / 146_097;
+	let doe = z - era * 146_097;
+	let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let day = doy - (153 * mp + 2) / 5 + 1;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+	format!(
+		"{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+		year,
+		month,
+		day,
+		secs_of_day / 3_600,
+		secs_of_day % 3_600 / 60,
+		secs_of_day % 60
+	)
+}
@@ -1,13 +1,21 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 
+use contract::buyout::BuyoutInfoSerial;
+use contract::minting::MintAuthoritySerial;
 use contract::sales::{SaleOptions, SaleOptionsSerial};
 use contract::types::MTTokenId;
 use contract::FEE_DENOMINATOR;
+use multi_token_standard::metadata::{
+    BaseTokenMetadata, MtContractMetadata, TokenMetadataAll, TokenMetadataPatch, MT_METADATA_SPEC,
+};
+use multi_token_standard::payout::Payout;
 use near_contract_standards::storage_management::{StorageBalance, StorageBalanceBounds};
 use near_sdk::env;
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{U128, U64};
 use near_sdk::serde::{self, Deserialize, Serialize};
 use near_sdk::serde_json::json;
+use near_sdk::AccountId;
 use near_sdk_sim::{call, to_yocto, transaction::ExecutionStatus, view, DEFAULT_GAS};
 
 use near_internal_balances_plugin::TokenId;
@@ -27,11 +35,28 @@ pub struct StorageBalanceTmp {
 pub const NFT_MINT_FEE: u128 = 1_000_000;
 pub const SALE_FEE_NUMERATOR: u128 = 100_000_000u128;
 const SUPPLY: u128 = 1_000_000_000_000_000;
+const MAX_SUPPLY: u128 = 2 * SUPPLY;
 
 fn init_with_fractionalize_nfts(
     sale_amount_whole: Option<U128>,
     sale_price_per_whole: Option<U128>,
     amount_frac_attach: Option<u128>,
+) -> (InitRet, Vec<TokenId>, MTTokenId) {
+    init_with_fractionalize_nfts_and_options(
+        sale_amount_whole,
+        sale_price_per_whole,
+        amount_frac_attach,
+        None,
+        None,
+    )
+}
+
+fn init_with_fractionalize_nfts_and_options(
+    sale_amount_whole: Option<U128>,
+    sale_price_per_whole: Option<U128>,
+    amount_frac_attach: Option<u128>,
+    royalty: Option<HashMap<AccountId, u32>>,
+    redemptions: Option<Vec<(String, U128)>>,
 ) -> (InitRet, Vec<TokenId>, MTTokenId) {
     let nfts = vec!["nft_1".to_string(), "nft_2".to_string()];
     let InitRet { alice, root, nft, contract } =
@@ -66,20 +91,32 @@ fn init_with_fractionalize_nfts(
         assert_eq!(bal.0, 1);
     }
 
+    let redemptions: Option<Vec<(TokenId, U128)>> = redemptions.map(|redemptions| {
+        redemptions
+            .into_iter()
+            .map(|(nft_id, amount)| {
+                (TokenId::NFT { contract_id: nft.account_id(), token_id: nft_id }, amount)
+            })
+            .collect()
+    });
+
     // Fractionalize them
     call!(
         root,
         contract.nft_fractionalize(
-            nfts_tok_ids.clone(),
+            nfts_tok_ids.iter().map(|tok| (tok.clone(), U128::from(1))).collect(),
             mt_id.clone(),
             U128::from(SUPPLY),
             None,
             get_default_metadata(),
             sale_amount_whole,
-            sale_price_per_whole
+            sale_price_per_whole,
+            Some(U128::from(MAX_SUPPLY)),
+            royalty,
+            redemptions
         ),
         deposit =
-            amount_frac_attach.unwrap_or(NFT_MINT_FEE + near_sdk::env::storage_byte_cost() * 1_000)
+            amount_frac_attach.unwrap_or(NFT_MINT_FEE + near_sdk::env::storage_byte_cost() * 2_000)
     )
     .assert_success();
     let bal_post_frac: U128 =
@@ -112,6 +149,408 @@ fn simulate_simple_fractionalization() {
     assert_eq!(bal_post_unwrap.0, 0);
 }
 
+#[test]
+fn simulate_unwrap_locked_fractions() {
+    let (InitRet { alice, root, nft: _, contract }, _, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+    call!(
+        root,
+        contract.mt_lock(mt_id.clone(), U128::from(10), alice.account_id(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+
+    // Locked fractions can't be burned by unwrapping
+    let outcome = call!(root, contract.nft_fractionalize_unwrap(mt_id.clone(), None), deposit = 1);
+    assert!(!outcome.is_ok());
+
+    call!(alice, contract.mt_unlock(mt_id.clone(), root.account_id(), None), deposit = 1)
+        .assert_success();
+    call!(root, contract.nft_fractionalize_unwrap(mt_id.clone(), None), deposit = 1)
+        .assert_success();
+}
+
+#[test]
+fn simulate_fractionalize_underlying_amounts() {
+    let (InitRet { alice: _, root, nft: _, contract }, nfts_tok_ids, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+    let underlying: Vec<(TokenId, U128)> =
+        view!(contract.nft_fractionalize_get_underlying(mt_id.clone())).unwrap_json();
+    assert_eq!(
+        underlying,
+        nfts_tok_ids.iter().map(|tok| (tok.clone(), U128::from(1))).collect::<Vec<_>>()
+    );
+    call!(root, contract.nft_fractionalize_unwrap(mt_id.clone(), None), deposit = 1)
+        .assert_success();
+
+    // An NFT can only be locked up with an amount of 1, and only once
+    let bad_underlying = vec![
+        vec![(nfts_tok_ids[0].clone(), U128::from(2))],
+        vec![(nfts_tok_ids[0].clone(), U128::from(1)), (nfts_tok_ids[0].clone(), U128::from(1))],
+    ];
+    for underlying in bad_underlying {
+        let outcome = call!(
+            root,
+            contract.nft_fractionalize(
+                underlying,
+                "MyNFTFRACED2".to_string(),
+                U128::from(SUPPLY),
+                None,
+                get_default_metadata(),
+                None,
+                None,
+                None,
+                None,
+                None
+            ),
+            deposit = NFT_MINT_FEE + near_sdk::env::storage_byte_cost() * 2_000
+        );
+        assert!(!outcome.is_ok());
+    }
+    for nft_tok in &nfts_tok_ids {
+        let bal: U128 =
+            view!(contract.internal_balance_get_balance(root.account_id(), nft_tok.clone()))
+                .unwrap_json();
+        assert_eq!(bal.0, 1);
+    }
+}
+
+#[test]
+fn simulate_fractionalize_mt_amount() {
+    let (InitRet { alice: _, root, nft: _, contract }, _, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+    let amount = SUPPLY / 4;
+
+    // Deposit part of the fractions with the contract to fractionalize them in turn
+    call!(
+        root,
+        contract.mt_transfer_call(
+            contract.account_id(),
+            mt_id.clone(),
+            U128::from(amount),
+            None,
+            None,
+            "".to_string()
+        ),
+        deposit = to_yocto("1")
+    )
+    .assert_success();
+    let mt_tok = TokenId::MT { contract_id: contract.account_id(), token_id: mt_id.clone() };
+    let bal: U128 = view!(contract.internal_balance_get_balance(root.account_id(), mt_tok.clone()))
+        .unwrap_json();
+    assert_eq!(bal.0, amount);
+
+    let mut metadata = get_default_metadata();
+    metadata.base.id = "bb".to_string();
+    call!(
+        root,
+        contract.nft_fractionalize(
+            vec![(mt_tok.clone(), U128::from(amount))],
+            "MyMTFRACED".to_string(),
+            U128::from(SUPPLY),
+            None,
+            metadata,
+            None,
+            None,
+            None,
+            None,
+            None
+        ),
+        deposit = NFT_MINT_FEE + env::storage_byte_cost() * 2_000
+    )
+    .assert_success();
+    let underlying: Vec<(TokenId, U128)> =
+        view!(contract.nft_fractionalize_get_underlying("MyMTFRACED".to_string())).unwrap_json();
+    assert_eq!(underlying, vec![(mt_tok.clone(), U128::from(amount))]);
+    let bal: U128 = view!(contract.internal_balance_get_balance(root.account_id(), mt_tok.clone()))
+        .unwrap_json();
+    assert_eq!(bal.0, 0);
+
+    // Unwrapping gives back exactly the amount locked up
+    call!(root, contract.nft_fractionalize_unwrap("MyMTFRACED".to_string(), None), deposit = 1)
+        .assert_success();
+    let bal: U128 = view!(contract.internal_balance_get_balance(root.account_id(), mt_tok.clone()))
+        .unwrap_json();
+    assert_eq!(bal.0, amount);
+    let held: U128 = view!(contract.balance_of(contract.account_id(), mt_id.clone())).unwrap_json();
+    assert_eq!(held.0, amount);
+}
+
+#[test]
+fn simulate_fractionalized_metadata() {
+    let (InitRet { alice: _, root: _, nft: _, contract }, _, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+    let contract_metadata: MtContractMetadata = view!(contract.mt_metadata_contract()).unwrap_json();
+    assert_eq!(contract_metadata.spec, MT_METADATA_SPEC.to_string());
+
+    let metadata: Vec<Option<TokenMetadataAll>> =
+        view!(contract.mt_metadata_token_all(vec![mt_id.clone(), "missing".to_string()]))
+            .unwrap_json();
+    assert_eq!(metadata, vec![Some(get_default_metadata()), None]);
+
+    let base: Vec<Option<BaseTokenMetadata>> =
+        view!(contract.mt_metadata_base_by_metadata_id(vec!["aa".to_string()])).unwrap_json();
+    assert_eq!(base, vec![Some(get_default_metadata().base)]);
+}
+
+#[test]
+fn simulate_update_metadata() {
+    let (InitRet { alice, root, nft: _, contract }, _, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+    let patch =
+        TokenMetadataPatch { title: Some(Some("Fixed title".to_string())), ..Default::default() };
+
+    let outcome = call!(
+        alice,
+        contract.mt_update_metadata(mt_id.clone(), patch.clone(), None),
+        deposit = to_yocto("0.01")
+    );
+    assert!(!outcome.is_ok());
+
+    call!(
+        root,
+        contract.mt_update_metadata(mt_id.clone(), patch.clone(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let metadata: Vec<Option<TokenMetadataAll>> =
+        view!(contract.mt_metadata_token_all(vec![mt_id.clone()])).unwrap_json();
+    let token = metadata[0].as_ref().unwrap().token.clone();
+    assert_eq!(token.title, Some("Fixed title".to_string()));
+    assert!(token.updated_at.is_some());
+
+    // Fields set to None are cleared, the ones left out are kept
+    let clear = TokenMetadataPatch { title: Some(None), ..Default::default() };
+    call!(
+        root,
+        contract.mt_update_metadata(mt_id.clone(), clear, None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let metadata: Vec<Option<TokenMetadataAll>> =
+        view!(contract.mt_metadata_token_all(vec![mt_id.clone()])).unwrap_json();
+    let cleared = metadata[0].as_ref().unwrap().token.clone();
+    assert_eq!(cleared.title, None);
+    assert_eq!(cleared.description, token.description);
+
+    call!(root, contract.mt_freeze_metadata(mt_id.clone()), deposit = 1).assert_success();
+    let frozen: bool = view!(contract.mt_is_metadata_frozen(mt_id.clone())).unwrap_json();
+    assert!(frozen);
+    let outcome = call!(
+        root,
+        contract.mt_update_metadata(mt_id.clone(), patch, None),
+        deposit = to_yocto("0.01")
+    );
+    assert!(!outcome.is_ok());
+}
+
+#[test]
+fn simulate_follow_on_minting() {
+    let (InitRet { alice, root, nft: _, contract }, _, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+    let authority: MintAuthoritySerial =
+        view!(contract.mt_mint_authority(mt_id.clone())).unwrap_json();
+    assert_eq!(
+        authority,
+        MintAuthoritySerial {
+            minter: Some(root.account_id()),
+            max_supply: Some(U128::from(MAX_SUPPLY))
+        }
+    );
+
+    call!(
+        root,
+        contract.mt_mint(mt_id.clone(), U128::from(SUPPLY / 2), None),
+        deposit = env::storage_byte_cost() * 1_000
+    )
+    .assert_success();
+    let supply: U128 = view!(contract.total_supply(mt_id.clone())).unwrap_json();
+    assert_eq!(supply.0, SUPPLY + SUPPLY / 2);
+
+    // Only the minter can mint and the max supply cannot be exceeded
+    let outcome = call!(
+        alice,
+        contract.mt_mint(mt_id.clone(), U128::from(1), None),
+        deposit = env::storage_byte_cost() * 1_000
+    );
+    assert!(!outcome.is_ok());
+    let outcome = call!(
+        root,
+        contract.mt_mint(mt_id.clone(), U128::from(SUPPLY), None),
+        deposit = env::storage_byte_cost() * 1_000
+    );
+    assert!(!outcome.is_ok());
+
+    // Burned tokens count towards the cap, so they cannot be minted again past it
+    call!(root, contract.mt_burn(mt_id.clone(), U128::from(SUPPLY / 2), None, None), deposit = 1)
+        .assert_success();
+    let outcome = call!(
+        root,
+        contract.mt_mint(mt_id.clone(), U128::from(SUPPLY / 2 + 1), None),
+        deposit = env::storage_byte_cost() * 1_000
+    );
+    assert!(!outcome.is_ok());
+    call!(
+        root,
+        contract.mt_mint(mt_id.clone(), U128::from(SUPPLY / 2), None),
+        deposit = env::storage_byte_cost() * 1_000
+    )
+    .assert_success();
+
+    call!(root, contract.mt_set_minter(mt_id.clone(), alice.account_id()), deposit = 1)
+        .assert_success();
+    call!(alice, contract.mt_renounce_minting(mt_id.clone()), deposit = 1).assert_success();
+    let authority: MintAuthoritySerial =
+        view!(contract.mt_mint_authority(mt_id.clone())).unwrap_json();
+    assert_eq!(authority.minter, None);
+    let outcome = call!(
+        alice,
+        contract.mt_mint(mt_id.clone(), U128::from(1), None),
+        deposit = env::storage_byte_cost() * 1_000
+    );
+    assert!(!outcome.is_ok());
+}
+
+#[test]
+fn simulate_transfer_registers_receiver() {
+    let (InitRet { alice, root, nft: _, contract }, _, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+
+    // Alice is not registered for the token and nothing is attached to pay for it
+    let outcome = call!(
+        root,
+        contract.mt_transfer(alice.account_id(), mt_id.clone(), U128::from(10), None, None),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+
+    let root_near_pre = root.account().unwrap().amount;
+    call!(
+        root,
+        contract.mt_transfer(alice.account_id(), mt_id.clone(), U128::from(10), None, None),
+        deposit = to_yocto("1")
+    )
+    .assert_success();
+    let bal_alice: U128 =
+        view!(contract.balance_of(alice.account_id(), mt_id.clone())).unwrap_json();
+    assert_eq!(bal_alice.0, 10);
+    // The deposit not used for the registration is refunded
+    assert!(root_near_pre - root.account().unwrap().amount < to_yocto("0.1"));
+    // What the registration cost is kept as Alice's storage balance
+    let registered: StorageBalance =
+        view!(contract.storage_balance_of(alice.account_id())).unwrap_json();
+    assert!(registered.total.0 > 0);
+    assert_eq!(registered.available.0, 0);
+
+    // Once registered, a transfer only needs the usual yoctoNEAR
+    call!(
+        root,
+        contract.mt_transfer(alice.account_id(), mt_id.clone(), U128::from(10), None, None),
+        deposit = 1
+    )
+    .assert_success();
+
+    // Unregistering gives the registration back to Alice to withdraw
+    call!(
+        alice,
+        contract.mt_transfer(root.account_id(), mt_id.clone(), U128::from(20), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    call!(alice, contract.storage_unregister(vec![mt_id.clone()], None), deposit = 1)
+        .assert_success();
+    let unregistered: StorageBalance =
+        view!(contract.storage_balance_of(alice.account_id())).unwrap_json();
+    assert_eq!(unregistered.total.0, registered.total.0);
+    assert!(unregistered.available.0 > 0);
+}
+
+#[test]
+fn simulate_royalty_payout() {
+    let royalty: HashMap<AccountId, u32> =
+        vec![(AccountId::try_from("alice".to_string()).unwrap(), 250)].into_iter().collect();
+    let (InitRet { alice, root, nft: _, contract }, _, mt_id) =
+        init_with_fractionalize_nfts_and_options(
+            None,
+            None,
+            Some(NFT_MINT_FEE + env::storage_byte_cost() * 3_000),
+            Some(royalty),
+            None,
+        );
+
+    // The seller of FT type tokens is not known to the view, only the royalties are listed
+    let payout: Payout =
+        view!(contract.mt_payout(mt_id.clone(), U128::from(100), U128::from(10_000), None))
+            .unwrap_json();
+    assert_eq!(payout.payout, vec![(alice.account_id(), U128::from(250))].into_iter().collect());
+
+    let outcome = call!(
+        root,
+        contract.mt_transfer_payout(
+            alice.account_id(),
+            mt_id.clone(),
+            U128::from(100),
+            None,
+            None,
+            U128::from(10_000),
+            Some(1)
+        ),
+        deposit = to_yocto("1")
+    );
+    assert!(!outcome.is_ok());
+
+    let payout: Payout = call!(
+        root,
+        contract.mt_transfer_payout(
+            alice.account_id(),
+            mt_id.clone(),
+            U128::from(100),
+            None,
+            None,
+            U128::from(10_000),
+            Some(2)
+        ),
+        deposit = to_yocto("1")
+    )
+    .unwrap_json();
+    let expected: HashMap<AccountId, U128> =
+        vec![(alice.account_id(), U128::from(250)), (root.account_id(), U128::from(9_750))]
+            .into_iter()
+            .collect();
+    assert_eq!(payout.payout, expected);
+    let bal_alice: U128 =
+        view!(contract.balance_of(alice.account_id(), mt_id.clone())).unwrap_json();
+    assert_eq!(bal_alice.0, 100);
+}
+
+#[test]
+fn simulate_transfer_registers_receiver_from_storage_balance() {
+    let (InitRet { alice, root, nft: _, contract }, _, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+    call!(
+        alice,
+        contract.storage_deposit(vec![], None, None),
+        deposit = env::storage_byte_cost() * 2_000
+    )
+    .assert_success();
+
+    call!(
+        root,
+        contract.mt_batch_transfer(
+            alice.account_id(),
+            vec![mt_id.clone()],
+            vec![U128::from(10)],
+            None,
+            None
+        ),
+        deposit = 1
+    )
+    .assert_success();
+    let bal_alice: U128 =
+        view!(contract.balance_of(alice.account_id(), mt_id.clone())).unwrap_json();
+    assert_eq!(bal_alice.0, 10);
+}
+
 #[test]
 fn simulate_nft_frac_sale() {
     let sale_amount_whole = 100;
@@ -217,7 +656,7 @@ fn simulate_nft_frac_sale() {
 
 #[test]
 #[should_panic(
-    expected = "Must attach 9090000000000001000000 yoctoNEAR to cover storage and/or fees"
+    expected = "Must attach 11710000000000001000000 yoctoNEAR to cover storage and/or fees"
 )]
 fn simulate_fractionalize_not_enough_attached() {
     let sale_amount_whole = 100;
@@ -248,3 +687,332 @@ fn simulate_fractionalize_too_much_attached_and_returns() {
     let near_bal = alice.account().unwrap().amount;
     assert!(alice_init_bal - near_bal < to_yocto("2"))
 }
+
+#[test]
+fn simulate_buyout_and_redeem() {
+    let (InitRet { alice, root, nft: _, contract }, nfts_tok_ids, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+    call!(
+        root,
+        contract.mt_transfer(alice.account_id(), mt_id.clone(), U128::from(SUPPLY / 4), None, None),
+        deposit = to_yocto("1")
+    )
+    .assert_success();
+
+    // No buyout can start before half of the supply voted on the reserve price
+    call!(
+        alice,
+        contract.buyout_vote_reserve_price(mt_id.clone(), Some(U128::from(to_yocto("20")))),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.voted, U128::from(SUPPLY / 4));
+    assert_eq!(info.reserve_price, None);
+    let outcome = call!(alice, contract.buyout_start(mt_id.clone()), deposit = to_yocto("20"));
+    assert!(!outcome.is_ok());
+
+    // The reserve price is the median of the votes weighted by balance
+    call!(
+        root,
+        contract.buyout_vote_reserve_price(mt_id.clone(), Some(U128::from(to_yocto("10")))),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.voted, U128::from(SUPPLY));
+    assert_eq!(info.reserve_price, Some(U128::from(to_yocto("10"))));
+    call!(root, contract.buyout_update_duration(U64::from(10_000_000_000))).assert_success();
+
+    let outcome = call!(alice, contract.buyout_start(mt_id.clone()), deposit = to_yocto("5"));
+    assert!(!outcome.is_ok());
+    call!(alice, contract.buyout_start(mt_id.clone()), deposit = to_yocto("10")).assert_success();
+    let outcome = call!(
+        alice,
+        contract.buyout_vote_reserve_price(mt_id.clone(), None),
+        deposit = to_yocto("0.01")
+    );
+    assert!(!outcome.is_ok());
+
+    // Outbidding refunds alice
+    let alice_near_pre = alice.account().unwrap().amount;
+    call!(root, contract.buyout_bid(mt_id.clone()), deposit = to_yocto("12")).assert_success();
+    assert_eq!(alice.account().unwrap().amount - alice_near_pre, to_yocto("10"));
+    let outcome = call!(alice, contract.buyout_bid(mt_id.clone()), deposit = to_yocto("12"));
+    assert!(!outcome.is_ok());
+    let outcome = call!(alice, contract.buyout_settle(mt_id.clone()));
+    assert!(!outcome.is_ok());
+
+    root.borrow_runtime_mut().produce_blocks(20).unwrap();
+    let outcome = call!(alice, contract.buyout_bid(mt_id.clone()), deposit = to_yocto("13"));
+    assert!(!outcome.is_ok());
+    call!(alice, contract.buyout_settle(mt_id.clone())).assert_success();
+    for nft_tok in &nfts_tok_ids {
+        let bal: U128 =
+            view!(contract.internal_balance_get_balance(root.account_id(), nft_tok.clone()))
+                .unwrap_json();
+        assert_eq!(bal.0, 1);
+    }
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.proceeds, U128::from(to_yocto("12")));
+    assert!(info.auction.unwrap().settled);
+
+    // Each holder burns their fractions for their share of the winning bid
+    let alice_near_pre = alice.account().unwrap().amount;
+    let share: U128 =
+        call!(alice, contract.buyout_redeem(mt_id.clone()), deposit = 1).unwrap_json();
+    assert_eq!(share.0, to_yocto("3"));
+    assert!(alice.account().unwrap().amount - alice_near_pre > to_yocto("2.9"));
+    let share: U128 = call!(root, contract.buyout_redeem(mt_id.clone()), deposit = 1).unwrap_json();
+    assert_eq!(share.0, to_yocto("9"));
+    let outcome = call!(alice, contract.buyout_redeem(mt_id.clone()), deposit = 1);
+    assert!(!outcome.is_ok());
+
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.proceeds, U128::from(0));
+    let supply: U128 = view!(contract.total_supply(mt_id.clone())).unwrap_json();
+    assert_eq!(supply.0, 0);
+}
+
+#[test]
+fn simulate_reserve_votes_follow_balances() {
+    let (InitRet { alice, root, nft: _, contract }, _, mt_id) =
+        init_with_fractionalize_nfts(None, None, None);
+    call!(
+        root,
+        contract.mt_transfer(alice.account_id(), mt_id.clone(), U128::from(SUPPLY / 4), None, None),
+        deposit = to_yocto("1")
+    )
+    .assert_success();
+    call!(
+        alice,
+        contract.buyout_vote_reserve_price(mt_id.clone(), Some(U128::from(to_yocto("20")))),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    call!(
+        root,
+        contract.buyout_vote_reserve_price(mt_id.clone(), Some(U128::from(to_yocto("10")))),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.reserve_price, Some(U128::from(to_yocto("10"))));
+
+    // Alice has no contract to take the fractions, so they are refunded. The vote of the sender
+    // was lowered when they left and the vote of the receiver still fits its balance.
+    call!(
+        root,
+        contract.mt_transfer_call(
+            alice.account_id(),
+            mt_id.clone(),
+            U128::from(SUPPLY / 4),
+            None,
+            None,
+            "".to_string()
+        ),
+        deposit = 1
+    )
+    .assert_success();
+    let bal: U128 = view!(contract.balance_of(root.account_id(), mt_id.clone())).unwrap_json();
+    assert_eq!(bal.0, SUPPLY * 3 / 4);
+    let vote: Option<U128> =
+        view!(contract.buyout_reserve_vote(mt_id.clone(), alice.account_id())).unwrap_json();
+    assert_eq!(vote, Some(U128::from(to_yocto("20"))));
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.voted, U128::from(SUPPLY * 3 / 4));
+    assert_eq!(info.reserve_price, Some(U128::from(to_yocto("10"))));
+
+    // Moving fractions away lowers the weight of a vote, the receiver's only grows by voting again
+    call!(
+        root,
+        contract.mt_transfer(alice.account_id(), mt_id.clone(), U128::from(SUPPLY / 2), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.voted, U128::from(SUPPLY / 2));
+    assert_eq!(info.reserve_price, Some(U128::from(to_yocto("10"))));
+    call!(
+        alice,
+        contract.buyout_vote_reserve_price(mt_id.clone(), Some(U128::from(to_yocto("20")))),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.voted, U128::from(SUPPLY));
+    assert_eq!(info.reserve_price, Some(U128::from(to_yocto("20"))));
+
+    // The vote of a holder left without enough fractions to vote is dropped
+    call!(
+        root,
+        contract.mt_transfer(alice.account_id(), mt_id.clone(), U128::from(SUPPLY / 4), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    let vote: Option<U128> =
+        view!(contract.buyout_reserve_vote(mt_id.clone(), root.account_id())).unwrap_json();
+    assert_eq!(vote, None);
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.voted, U128::from(SUPPLY * 3 / 4));
+
+    // Dust can't vote
+    call!(
+        alice,
+        contract.mt_transfer(root.account_id(), mt_id.clone(), U128::from(1), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    let outcome = call!(
+        root,
+        contract.buyout_vote_reserve_price(mt_id.clone(), Some(U128::from(to_yocto("1")))),
+        deposit = to_yocto("0.01")
+    );
+    assert!(!outcome.is_ok());
+
+    // Withdrawn votes no longer count towards the quorum
+    call!(alice, contract.buyout_vote_reserve_price(mt_id.clone(), None), deposit = 1)
+        .assert_success();
+    let vote: Option<U128> =
+        view!(contract.buyout_reserve_vote(mt_id.clone(), alice.account_id())).unwrap_json();
+    assert_eq!(vote, None);
+    let info: BuyoutInfoSerial = view!(contract.buyout_info(mt_id.clone())).unwrap_json();
+    assert_eq!(info.voted, U128::from(0));
+    assert_eq!(info.reserve_price, None);
+}
+
+#[test]
+fn simulate_partial_redemption() {
+    let (InitRet { alice, root, nft: _, contract }, nfts_tok_ids, mt_id) =
+        init_with_fractionalize_nfts_and_options(
+            None,
+            None,
+            Some(NFT_MINT_FEE + env::storage_byte_cost() * 3_000),
+            None,
+            Some(vec![
+                ("nft_1".to_string(), U128::from(SUPPLY / 2)),
+                ("nft_2".to_string(), U128::from(SUPPLY / 2)),
+            ]),
+        );
+    let redemptions: Vec<(TokenId, U128)> =
+        view!(contract.nft_fractionalize_get_redemptions(mt_id.clone())).unwrap_json();
+    assert_eq!(
+        redemptions,
+        vec![
+            (nfts_tok_ids[0].clone(), U128::from(SUPPLY / 2)),
+            (nfts_tok_ids[1].clone(), U128::from(SUPPLY / 2))
+        ]
+    );
+
+    // The redemption amounts are set against the supply, which can't grow while any are left
+    let outcome = call!(
+        root,
+        contract.mt_mint(mt_id.clone(), U128::from(SUPPLY), None),
+        deposit = env::storage_byte_cost() * 1_000
+    );
+    assert!(!outcome.is_ok());
+    call!(
+        root,
+        contract.mt_transfer(alice.account_id(), mt_id.clone(), U128::from(SUPPLY / 8), None, None),
+        deposit = to_yocto("1")
+    )
+    .assert_success();
+
+    // Alice holds too few fractions
+    let outcome = call!(
+        alice,
+        contract.nft_fractionalize_redeem(mt_id.clone(), nfts_tok_ids[0].clone()),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+
+    call!(
+        root,
+        contract.nft_fractionalize_redeem(mt_id.clone(), nfts_tok_ids[0].clone()),
+        deposit = 1
+    )
+    .assert_success();
+    let bal: U128 =
+        view!(contract.internal_balance_get_balance(root.account_id(), nfts_tok_ids[0].clone()))
+            .unwrap_json();
+    assert_eq!(bal.0, 1);
+    let supply: U128 = view!(contract.total_supply(mt_id.clone())).unwrap_json();
+    assert_eq!(supply.0, SUPPLY / 2);
+    let underlying: Vec<(TokenId, U128)> =
+        view!(contract.nft_fractionalize_get_underlying(mt_id.clone())).unwrap_json();
+    assert_eq!(underlying, vec![(nfts_tok_ids[1].clone(), U128::from(1))]);
+
+    // Each token is only redeemed once
+    let outcome = call!(
+        root,
+        contract.nft_fractionalize_redeem(mt_id.clone(), nfts_tok_ids[0].clone()),
+        deposit = 1
+    );
+    assert!(!outcome.is_ok());
+
+    // Redeeming the last token burns the rest of the supply and closes the vault like unwrapping
+    call!(
+        alice,
+        contract.mt_transfer(root.account_id(), mt_id.clone(), U128::from(SUPPLY / 8), None, None),
+        deposit = 1
+    )
+    .assert_success();
+    call!(
+        root,
+        contract.nft_fractionalize_redeem(mt_id.clone(), nfts_tok_ids[1].clone()),
+        deposit = 1
+    )
+    .assert_success();
+    let bal: U128 =
+        view!(contract.internal_balance_get_balance(root.account_id(), nfts_tok_ids[1].clone()))
+            .unwrap_json();
+    assert_eq!(bal.0, 1);
+    let underlying: Vec<(TokenId, U128)> =
+        view!(contract.nft_fractionalize_get_underlying(mt_id.clone())).unwrap_json();
+    assert!(underlying.is_empty());
+    let authority: MintAuthoritySerial =
+        view!(contract.mt_mint_authority(mt_id.clone())).unwrap_json();
+    assert_eq!(authority.minter, None);
+    let outcome = call!(
+        root,
+        contract.mt_mint(mt_id.clone(), U128::from(1), None),
+        deposit = env::storage_byte_cost() * 1_000
+    );
+    assert!(!outcome.is_ok());
+    let outcome = call!(root, contract.nft_fractionalize_unwrap(mt_id.clone(), None), deposit = 1);
+    assert!(!outcome.is_ok());
+    let supply: U128 = view!(contract.total_supply(mt_id.clone())).unwrap_json();
+    assert_eq!(supply.0, 0);
+
+    // The redemption amounts can't add up to more than the supply, nor to less when every
+    // underlying token is listed
+    let bad_redemptions = vec![
+        vec![
+            (nfts_tok_ids[0].clone(), U128::from(SUPPLY / 2)),
+            (nfts_tok_ids[1].clone(), U128::from(SUPPLY / 2 + 1)),
+        ],
+        vec![
+            (nfts_tok_ids[0].clone(), U128::from(SUPPLY / 4)),
+            (nfts_tok_ids[1].clone(), U128::from(SUPPLY / 4)),
+        ],
+    ];
+    for redemptions in bad_redemptions {
+        let outcome = call!(
+            root,
+            contract.nft_fractionalize(
+                nfts_tok_ids.iter().map(|tok| (tok.clone(), U128::from(1))).collect(),
+                "MyNFTFRACED2".to_string(),
+                U128::from(SUPPLY),
+                None,
+                get_default_metadata(),
+                None,
+                None,
+                None,
+                None,
+                Some(redemptions)
+            ),
+            deposit = NFT_MINT_FEE + env::storage_byte_cost() * 3_000
+        );
+        assert!(!outcome.is_ok());
+    }
+}
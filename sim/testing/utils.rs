@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use contract::ContractContract;
-use multi_token_standard::metadata::MultiTokenMetadata;
+use multi_token_standard::metadata::{BaseTokenMetadata, TokenMetadata, TokenMetadataAll};
 use near_sdk::serde_json::json;
 use near_sdk::{json_types::U128, AccountId};
 use near_sdk_sim::{
@@ -28,26 +28,20 @@ pub const NFT_ID: &str = "nft";
 
 pub const INIT_USER_BAL_NEAR: &str = "100";
 
-pub fn get_default_metadata() -> MultiTokenMetadata {
-    multi_token_standard::metadata::MultiTokenMetadata {
-        spec: "aa".to_string(),   // required, essentially a version like "mt-1.0.0"
-        name: "aa".to_string(),   // required, ex. "Mosaics"
-        symbol: "aa".to_string(), // required, ex. "MOSIAC"
-        icon: None,               // Data URL
-        base_uri: None, // Centralized gateway known to have reliable access to decentralized storage assets referenced by `reference` or `media` URLs
-        decimals: Some(12), // precision decimals for tokens that need this information
-        reference: None, // URL to a JSON file with more info
-        reference_hash: None, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
-        title: None,          // ex. "Arch Nemesis: Mail Carrier" or "Parcel #5055"
-        description: None,    // free-form description
-        media: None, // URL to associated media, preferably to decentralized, content-addressed storage
-        media_hash: None, // Base64-encoded sha256 hash of content referenced by the `media` field. Required if `media` is included.
-        copies: None, // number of copies of this set of metadata in existence when token was minted.
-        issued_at: None, // ISO 8601 datetime when token was issued or minted
-        expires_at: None, // ISO 8601 datetime when token expires
-        starts_at: None, // ISO 8601 datetime when token starts being valid
-        updated_at: None, // ISO 8601 datetime when token was last updated
-        extra: None,  // anything extra the NFT wants to store on-chain. Can be stringified JSON.
+pub fn get_default_metadata() -> TokenMetadataAll {
+    TokenMetadataAll {
+        base: BaseTokenMetadata {
+            id: "aa".to_string(),   // required, unique id of this base metadata
+            name: "aa".to_string(), // required, ex. "Mosaics"
+            symbol: Some("aa".to_string()), // ex. "MOSIAC"
+            icon: None,             // Data URL
+            decimals: Some(12), // precision decimals for tokens that need this information
+            base_uri: None, // Centralized gateway known to have reliable access to decentralized storage assets referenced by `reference` or `media` URLs
+            reference: None, // URL to a JSON file with more info
+            reference_hash: None, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
+            copies: None, // number of copies of this set of metadata in existence when token was minted.
+        },
+        token: TokenMetadata::default(),
     }
 }
 
@@ -80,7 +74,7 @@ pub fn init_with_macros(nfts_to_mint: Vec<String>, nft_mint_fee: u128, sale_fee:
         // User deploying the contract,
         signer_account: root,
         // init method
-        init_method: new(Some(root.account_id()), Some(AccountId::from_str("alice").unwrap()), Some(U128::from(nft_mint_fee)), Some(U128::from(sale_fee)))
+        init_method: new(Some(root.account_id()), Some(AccountId::from_str("alice").unwrap()), Some(U128::from(nft_mint_fee)), Some(U128::from(sale_fee)), None)
     );
 
     let nft = deploy!(